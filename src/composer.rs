@@ -0,0 +1,239 @@
+//! Converting romaji to kana while it is being typed, see [`Composer`].
+
+use crate::options::Options;
+use crate::to_kana::{is_incomplete, match_len, to_kana_with_opt};
+use crate::utils::is_char_zenkaku_letter::zenkaku_to_ascii;
+
+/// Converts romaji to kana one keystroke at a time, like the composition of an input method.
+///
+/// Keys are converted as soon as no further key could change their conversion. Until then they
+/// stay in a pending buffer, e.g. `k`, `ky` or a trailing `n`, which is either extended (`nya` →
+/// にゃ), converted on its own (`nk` → んk, `nn` → ん) or converted when the text is committed.
+/// The kana converted so far and the pending keys together make up the preedit text shown while
+/// typing.
+///
+/// The conversion uses the same [`Options`] as to_kana(), with
+/// [`Options::imemode`](crate::Options::imemode) always set.
+///
+/// # Examples
+/// ```
+/// use wana_kana::Composer;
+/// let mut composer = Composer::new();
+/// composer.push_str("kyouhan");
+/// assert_eq!(composer.kana(), "きょうは");
+/// assert_eq!(composer.pending(), "n");
+/// assert_eq!(composer.preedit(), "きょうはn");
+/// composer.backspace();
+/// composer.push_str("re");
+/// assert_eq!(composer.commit(), "きょうはれ");
+/// assert!(composer.is_empty());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Composer {
+    options: Options,
+    kana: String,
+    pending: String,
+}
+
+impl Composer {
+    /// Creates an empty composer converting with the default options.
+    pub fn new() -> Self {
+        Self::with_opt(Options::default())
+    }
+
+    /// Creates an empty composer converting with the given options.
+    pub fn with_opt(options: Options) -> Self {
+        Composer {
+            options: Options {
+                imemode: true,
+                ..options
+            },
+            kana: String::new(),
+            pending: String::new(),
+        }
+    }
+
+    /// The kana converted from the keys so far.
+    pub fn kana(&self) -> &str {
+        &self.kana
+    }
+
+    /// The keys which have not been converted yet.
+    pub fn pending(&self) -> &str {
+        &self.pending
+    }
+
+    /// The text to show while typing, the converted kana followed by the pending keys.
+    pub fn preedit(&self) -> String {
+        format!("{}{}", self.kana, self.pending)
+    }
+
+    /// Whether there is neither converted kana nor a pending key.
+    pub fn is_empty(&self) -> bool {
+        self.kana.is_empty() && self.pending.is_empty()
+    }
+
+    /// Types a key.
+    pub fn push(&mut self, key: char) {
+        self.pending.push(key);
+        self.convert_pending();
+    }
+
+    /// Types each char of `keys` in turn.
+    pub fn push_str(&mut self, keys: &str) {
+        for key in keys.chars() {
+            self.push(key);
+        }
+    }
+
+    /// Deletes the last pending key, or the last kana if there is none. Returns the deleted char.
+    pub fn backspace(&mut self) -> Option<char> {
+        self.pending.pop().or_else(|| self.kana.pop())
+    }
+
+    /// Converts the pending keys as if the input ended there, e.g. a trailing `n` becomes ん, and
+    /// returns the whole text, leaving the composer empty.
+    pub fn commit(&mut self) -> String {
+        let pending = std::mem::take(&mut self.pending);
+        let mut text = std::mem::take(&mut self.kana);
+        text.push_str(&to_kana_with_opt(&pending, self.final_options()));
+        text
+    }
+
+    /// Discards the converted kana and the pending keys.
+    pub fn clear(&mut self) {
+        self.kana.clear();
+        self.pending.clear();
+    }
+
+    /// The options outside of IME mode, where a lone n is ん.
+    fn final_options(&self) -> Options {
+        Options {
+            imemode: false,
+            ..self.options.clone()
+        }
+    }
+
+    fn convert_pending(&mut self) {
+        loop {
+            let chars = self
+                .pending
+                .chars()
+                .map(zenkaku_to_ascii)
+                .collect::<Vec<_>>();
+            if chars.is_empty() || is_incomplete(&chars, &self.options) {
+                return;
+            }
+            // In IME mode n has no conversion on its own, it's still ん before a consonant
+            let (len, options) = match match_len(&chars, &self.options) {
+                0 => {
+                    let options = self.final_options();
+                    (match_len(&chars, &options), options)
+                }
+                len => (len, self.options.clone()),
+            };
+            let split = self
+                .pending
+                .char_indices()
+                .nth(len.max(1))
+                .map_or(self.pending.len(), |(index, _)| index);
+            let rest = self.pending.split_off(split);
+            self.kana
+                .push_str(&to_kana_with_opt(&self.pending, options));
+            self.pending = rest;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::KatakanaWidth;
+
+    fn typed(keys: &str) -> Composer {
+        let mut composer = Composer::new();
+        composer.push_str(keys);
+        composer
+    }
+
+    #[test]
+    fn keeps_keys_pending_until_they_are_complete() {
+        let mut composer = Composer::new();
+        for (key, kana, pending) in [
+            ('k', "", "k"),
+            ('y', "", "ky"),
+            ('o', "きょ", ""),
+            ('t', "きょ", "t"),
+            ('t', "きょ", "tt"),
+            ('o', "きょっと", ""),
+        ] {
+            composer.push(key);
+            assert_eq!((composer.kana(), composer.pending()), (kana, pending));
+        }
+    }
+
+    #[test]
+    fn syllabic_n() {
+        assert_eq!(typed("n").pending(), "n");
+        assert_eq!(typed("nya").kana(), "にゃ");
+        assert_eq!(typed("nn").kana(), "ん");
+        assert_eq!(typed("n'").kana(), "ん");
+        assert_eq!(typed("n ").kana(), "ん");
+        let composer = typed("honk");
+        assert_eq!((composer.kana(), composer.pending()), ("ほん", "k"));
+        assert_eq!(typed("kantan").preedit(), "かんたn");
+        assert_eq!(typed("kantan").commit(), "かんたん");
+    }
+
+    #[test]
+    fn passes_through_other_keys() {
+        assert_eq!(typed("ka!").kana(), "か！");
+        assert_eq!(typed("q").pending(), "q");
+        assert_eq!(typed("q1").kana(), "q1");
+        assert_eq!(typed("漢字").kana(), "漢字");
+        assert_eq!(typed("ｋａ").kana(), "か");
+    }
+
+    #[test]
+    fn uses_the_options() {
+        assert_eq!(typed("KATAkana").kana(), "カタかな");
+        let mut composer = Composer::with_opt(Options {
+            katakana_width: KatakanaWidth::Half,
+            ..Default::default()
+        });
+        composer.push_str("GASU");
+        assert_eq!(composer.kana(), "ｶﾞｽ");
+        let mut composer = Composer::with_opt(Options {
+            use_obsolete_kana: true,
+            ..Default::default()
+        });
+        composer.push_str("wi");
+        assert_eq!(composer.kana(), "ゐ");
+    }
+
+    #[test]
+    fn backspace() {
+        let mut composer = typed("kak");
+        assert_eq!(composer.backspace(), Some('k'));
+        assert_eq!(composer.backspace(), Some('か'));
+        assert_eq!(composer.backspace(), None);
+        assert!(composer.is_empty());
+        composer.push_str("ky");
+        composer.backspace();
+        composer.push('a');
+        assert_eq!(composer.kana(), "か");
+    }
+
+    #[test]
+    fn commit() {
+        let mut composer = typed("kyo");
+        composer.push('k');
+        assert_eq!(composer.commit(), "きょk");
+        assert!(composer.is_empty());
+        composer.push_str("a");
+        assert_eq!(composer.commit(), "あ");
+        let mut composer = typed("sushi");
+        composer.clear();
+        assert_eq!(composer.commit(), "");
+    }
+}
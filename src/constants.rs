@@ -6,6 +6,8 @@ pub const CJK_SYMBOLS_PUNCTUATION: [u32; 2] = [0x3000, 0x303F];
 pub const KATAKANA_PUNCTUATION: [u32; 2] = [0x30FB, 0x30FC];
 pub const HIRAGANA_CHARS: [u32; 2] = [0x3040, 0x309F];
 pub const KATAKANA_CHARS: [u32; 2] = [0x30A0, 0x30FF];
+/// Small katakana used for Ainu (ㇰ, ㇱ, ㇷ, …)
+pub const KATAKANA_PHONETIC_EXTENSIONS: [u32; 2] = [0x31F0, 0x31FF];
 
 pub const LOWERCASE_ZENKAKU_START: u32 = 0xff41;
 pub const LOWERCASE_ZENKAKU_END: u32 = 0xff5a;
@@ -19,8 +21,26 @@ pub const ZENKAKU_PUNCTUATION_4: [u32; 2] = [0xFF5B, 0xFF60];
 pub const ZENKAKU_UPPERCASE: [u32; 2] = [UPPERCASE_ZENKAKU_START, UPPERCASE_ZENKAKU_END];
 pub const ZENKAKU_LOWERCASE: [u32; 2] = [LOWERCASE_ZENKAKU_START, LOWERCASE_ZENKAKU_END];
 pub const ZENKAKU_SYMBOLS_CURRENCY: [u32; 2] = [0xFFE0, 0xFFEE];
+/// Fullwidth forms of the printable ASCII chars except the space (！ to ～)
+pub const ZENKAKU_ASCII: [u32; 2] = [0xFF01, 0xFF5E];
+/// Offset between [`ZENKAKU_ASCII`] and the printable ASCII chars
+pub const ZENKAKU_ASCII_OFFSET: u32 = 0xFEE0;
 pub const KANA_PUNCTUATION: [u32; 2] = [0xFF61, 0xFF65];
 pub const HANKAKU_KATAKANA: [u32; 2] = [0xFF66, 0xFF9F];
+/// Katakana for Taiwanese (assigned chars of the Kana Extended-B block)
+pub const KANA_EXTENDED_B: [[u32; 2]; 3] =
+    [[0x1AFF0, 0x1AFF3], [0x1AFF5, 0x1AFFB], [0x1AFFD, 0x1AFFE]];
+/// Hentaigana and archaic kana (assigned chars of the Kana Supplement and Kana Extended-A blocks)
+pub const KANA_SUPPLEMENT: [u32; 2] = [0x1B000, 0x1B122];
+/// Hentaigana in the Kana Supplement and Kana Extended-A blocks
+pub const HENTAIGANA: [u32; 2] = [0x1B002, 0x1B11E];
+/// Small ゐ, ゑ, を, こ and ン (assigned chars of the Small Kana Extension block)
+pub const SMALL_KANA_EXTENSION: [[u32; 2]; 4] = [
+    [0x1B132, 0x1B132],
+    [0x1B150, 0x1B152],
+    [0x1B155, 0x1B155],
+    [0x1B164, 0x1B167],
+];
 pub const COMMON_CJK: [u32; 2] = [0x4E00, 0x9FFF];
 pub const RARE_CJK: [u32; 2] = [0x3400, 0x4DBF];
 pub const LATIN_NUMBERS: [u32; 2] = [0x0030, 0x0039];
@@ -55,12 +75,21 @@ pub const JA_PUNCTUATION_RANGES: [[u32; 2]; 8] = [
     ZENKAKU_SYMBOLS_CURRENCY,
 ];
 
-pub const KANA_RANGES: [[u32; 2]; 4] = [
+pub const KANA_RANGES: [[u32; 2]; 13] = [
     // const KANA_RANGES = [
     HIRAGANA_CHARS,
     KATAKANA_CHARS,
+    KATAKANA_PHONETIC_EXTENSIONS,
     KANA_PUNCTUATION,
     HANKAKU_KATAKANA,
+    KANA_EXTENDED_B[0],
+    KANA_EXTENDED_B[1],
+    KANA_EXTENDED_B[2],
+    KANA_SUPPLEMENT,
+    SMALL_KANA_EXTENSION[0],
+    SMALL_KANA_EXTENSION[1],
+    SMALL_KANA_EXTENSION[2],
+    SMALL_KANA_EXTENSION[3],
 ];
 
 lazy_static! {
@@ -96,3 +125,6 @@ pub const KATAKANA_START: u32 = 0x30A1;
 pub const KATAKANA_END: u32 = 0x30FC;
 pub const PROLONGED_SOUND_MARK: u32 = 0x30FC;
 pub const KANA_SLASH_DOT: u32 = 0x30FB;
+
+/// Marks the following は, へ or を as a particle for to_romaji(), see `Options::particles`
+pub const PARTICLE_MARKER: char = '_';
@@ -0,0 +1,144 @@
+//! Mappings that override or extend the built-in conversion tables
+
+use std::sync::Arc;
+
+use crate::utils::katakana_to_hiragana::katakana_to_hiragana;
+
+/// A trie of custom mappings, which is looked up together with the built-in conversion tables.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct MappingNode {
+    pub transitions: Vec<(char, MappingNode)>,
+    pub output: Option<String>,
+}
+
+impl MappingNode {
+    fn from_mapping<I, K, V>(mapping: I, normalize_key: fn(&str) -> String) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Into<String>,
+    {
+        let mut root = MappingNode::default();
+        for (key, output) in mapping {
+            let key = normalize_key(key.as_ref());
+            if !key.is_empty() {
+                root.insert(&key, output.into());
+            }
+        }
+        root
+    }
+
+    pub(crate) fn insert(&mut self, key: &str, output: String) {
+        let mut curr_node = self;
+        for char in key.chars() {
+            let index = match curr_node.transitions.iter().position(|t| t.0 == char) {
+                Some(index) => index,
+                None => {
+                    curr_node.transitions.push((char, MappingNode::default()));
+                    curr_node.transitions.len() - 1
+                }
+            };
+            curr_node = &mut curr_node.transitions[index].1;
+        }
+        curr_node.output = Some(output);
+    }
+
+    /// Removes the output of the node reached by `key`, returning it.
+    pub(crate) fn take_output(&mut self, key: &str) -> Option<String> {
+        let mut curr_node = self;
+        for char in key.chars() {
+            curr_node = &mut curr_node.transitions.iter_mut().find(|t| t.0 == char)?.1;
+        }
+        curr_node.output.take()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.transitions.is_empty() && self.output.is_none()
+    }
+
+    pub(crate) fn find_transition_node(&self, char: char) -> Option<&MappingNode> {
+        self.transitions.iter().find(|t| t.0 == char).map(|t| &t.1)
+    }
+}
+
+/// Romaji to kana mappings that override or extend the ones of to_kana(), to_hiragana() and
+/// to_katakana().
+///
+/// The mappings are merged into the conversion table selected by the other options, so the
+/// longest match still wins: with `la` → ら, `lalya` becomes らゃ. Romaji are matched case
+/// insensitively, uppercase input still results in katakana.
+///
+/// # Examples
+/// ```
+/// use wana_kana::{ConvertJapanese, CustomKanaMapping, Options};
+/// let options = Options {
+///     custom_kana_mapping: Some(CustomKanaMapping::new([("wi", "ゐ"), ("la", "ら")])),
+///     ..Default::default()
+/// };
+/// assert_eq!("wilalu".to_kana_with_opt(options.clone()), "ゐらぅ");
+/// assert_eq!("WILA".to_katakana_with_opt(options), "ヰラ");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CustomKanaMapping(pub(crate) Arc<MappingNode>);
+
+impl CustomKanaMapping {
+    /// Builds the mapping from pairs of romaji and kana.
+    pub fn new<I, K, V>(mapping: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Into<String>,
+    {
+        CustomKanaMapping(Arc::new(MappingNode::from_mapping(
+            mapping,
+            str::to_lowercase,
+        )))
+    }
+}
+
+impl<K: AsRef<str>, V: Into<String>> FromIterator<(K, V)> for CustomKanaMapping {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(mapping: I) -> Self {
+        CustomKanaMapping::new(mapping)
+    }
+}
+
+/// Kana to romaji mappings that override or extend the ones of to_romaji().
+///
+/// The mappings are merged into the conversion table of the selected
+/// [`Romanization`](crate::Romanization), so the longest match still wins: with ん → m, ほんや is
+/// still romanized as hon'ya since んや has its own mapping. Katakana are mapped like the same
+/// hiragana.
+///
+/// # Examples
+/// ```
+/// use wana_kana::{ConvertJapanese, CustomRomajiMapping, Options};
+/// let options = Options {
+///     custom_romaji_mapping: Some(CustomRomajiMapping::new([("じ", "zi"), ("ふ", "hu")])),
+///     ..Default::default()
+/// };
+/// assert_eq!("ふじさん".to_romaji_with_opt(options.clone()), "huzisan");
+/// assert_eq!("じゃ".to_romaji_with_opt(options), "ja");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CustomRomajiMapping(pub(crate) Arc<MappingNode>);
+
+impl CustomRomajiMapping {
+    /// Builds the mapping from pairs of kana and romaji.
+    pub fn new<I, K, V>(mapping: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Into<String>,
+    {
+        CustomRomajiMapping(Arc::new(MappingNode::from_mapping(
+            mapping,
+            katakana_to_hiragana,
+        )))
+    }
+}
+
+impl<K: AsRef<str>, V: Into<String>> FromIterator<(K, V)> for CustomRomajiMapping {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(mapping: I) -> Self {
+        CustomRomajiMapping::new(mapping)
+    }
+}
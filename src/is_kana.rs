@@ -25,6 +25,14 @@ mod tests {
         assert!(is_kana("ア"));
     }
     #[test]
+    fn halfwidth_katakana_is_kana() {
+        assert!(is_kana("ｶﾞｿﾘﾝ"));
+    }
+    #[test]
+    fn hentaigana_is_kana() {
+        assert!(is_kana("𛂇𛂺ん"));
+    }
+    #[test]
     fn あア_is_kana() {
         assert!(is_kana("あア"));
     }
@@ -1,6 +1,8 @@
 #[cfg(feature = "enable_regex")]
 use regex::Regex;
 
+use crate::constants::ZENKAKU_ASCII;
+use crate::utils::is_char_in_range::*;
 use crate::utils::is_char_romaji::*;
 
 /// Test if every char in `input` is [Romaji](https://en.wikipedia.org/wiki/Romaji) (allowing [Hepburn romanisation](https://en.wikipedia.org/wiki/Hepburn_romanization))
@@ -11,6 +13,23 @@ pub fn is_romaji(input: &str) -> bool {
     input.chars().all(is_char_romaji)
 }
 
+/// Test if every char in `input` is [Romaji](https://en.wikipedia.org/wiki/Romaji), also
+/// accepting Zenkaku latin letters, numbers, punctuation and spaces (ｈｅｌｌｏ！) if
+/// `allow_zenkaku` is true
+pub fn is_romaji_allow_zenkaku(input: &str, allow_zenkaku: bool) -> bool {
+    if !allow_zenkaku {
+        return is_romaji(input);
+    }
+    if input.is_empty() {
+        return false;
+    }
+    input.chars().all(|char| {
+        is_char_romaji(char)
+            || char == '\u{3000}'
+            || is_char_in_range(char, ZENKAKU_ASCII[0], ZENKAKU_ASCII[1])
+    })
+}
+
 #[cfg(feature = "enable_regex")]
 /// Test if every char in `input` is [Romaji](https://en.wikipedia.org/wiki/Romaji) or matches the
 /// provided regex
@@ -89,6 +108,18 @@ mod tests {
     fn fails_zenkaku_latin() {
         assert!(!is_romaji("ｈｅｌｌｏ"));
     }
+    #[test]
+    fn passes_zenkaku_when_allowed() {
+        assert!(is_romaji_allow_zenkaku("ｈｅｌｌｏ", true));
+        assert!(is_romaji_allow_zenkaku(
+            "ｈｅｌｌｏ ｗｏｒｌｄ！ １２３ and ascii",
+            true
+        ));
+        assert!(!is_romaji_allow_zenkaku("ｈｅｌｌｏ", false));
+        assert!(is_romaji_allow_zenkaku("ｈｅｌｌｏ　ｗｏｒｌｄ", true));
+        assert!(!is_romaji_allow_zenkaku("ひらがな", true));
+        assert!(!is_romaji_allow_zenkaku("", true));
+    }
 
     #[cfg(feature = "enable_regex")]
     #[test]
@@ -24,6 +24,11 @@
 //! assert_eq!("toukyou,おおさか".to_katakana(), "トウキョウ、オオサカ");
 //! // to romaji
 //! assert_eq!("ひらがな　カタカナ".to_romaji(), "hiragana katakana");
+//! // between fullwidth and halfwidth ASCII
+//! assert_eq!("ＡＢＣ１２３！".to_halfwidth(), "ABC123!");
+//! assert_eq!("ABC123!".to_fullwidth(), "ＡＢＣ１２３！");
+//! // all forms an IME offers for the romaji being typed
+//! assert_eq!("kyou".to_ime_candidates().halfwidth_katakana, "ｷｮｳ");
 //! ```
 //!
 //! # Checks
@@ -67,11 +72,13 @@ pub(crate) mod is_mixed;
 pub(crate) mod is_romaji;
 
 pub(crate) mod to_hiragana;
+pub(crate) mod to_ime_candidates;
 pub(crate) mod to_kana;
 pub(crate) mod to_kana_node_tree;
 pub(crate) mod to_katakana;
 pub(crate) mod to_romaji;
 pub(crate) mod to_romaji_node_tree;
+pub(crate) mod to_width;
 
 #[cfg_attr(docsrs, doc(cfg(feature = "tokenize")))]
 #[cfg(feature = "tokenize")]
@@ -80,11 +87,27 @@ pub mod tokenize;
 #[cfg(feature = "tokenize")]
 pub mod trim_okurigana;
 
+mod composer;
 #[allow(missing_docs)]
 pub mod constants;
+mod custom_mapping;
 mod options;
-
-pub use crate::options::Options;
+pub mod passport;
+mod scheme;
+
+pub use crate::composer::Composer;
+pub use crate::custom_mapping::{CustomKanaMapping, CustomRomajiMapping};
+pub use crate::options::{
+    FinalSokuon, KatakanaWidth, LongVowels, Options, Romanization, Separator, SyllabicN,
+    WidthOptions,
+};
+pub use crate::scheme::Scheme;
+#[cfg_attr(docsrs, doc(cfg(feature = "schemes")))]
+#[cfg(feature = "schemes")]
+pub use crate::scheme::SchemeError;
+pub use crate::to_ime_candidates::ImeCandidates;
+pub use crate::to_kana::{UnconvertedReason, UnconvertedRomaji, UnconvertedSpan};
+pub use crate::to_romaji::{PassthroughCategory, PassthroughSpan, RomajiReport};
 
 pub mod traits;
 pub use traits::{ConvertJapanese, IsJapaneseChar, IsJapaneseStr};
@@ -128,6 +151,17 @@ mod proptests {
             let _ = &input.to_hiragana();
         }
 
+        #[test]
+        fn wapuro_romaji_round_trips_through_to_kana(
+            input in r"[ぁ-ゖァ-ヺー]{1,100}"
+        ) {
+            let romaji = input.to_romaji_with_opt(Options {
+                romanization: Romanization::Wapuro,
+                ..Default::default()
+            });
+            prop_assert_eq!(romaji.to_kana(), input);
+        }
+
         #[test]
         fn mixed_romaji_kana_katakana_input_does_not_panic(
             input in r"[A-Za-zぁ-ゟァ-ヿー]{1,100}"
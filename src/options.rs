@@ -1,3 +1,6 @@
+use crate::custom_mapping::{CustomKanaMapping, CustomRomajiMapping};
+use crate::scheme::Scheme;
+
 #[derive(Debug, Default, Clone)]
 /// Options to set.
 pub struct Options {
@@ -15,4 +18,251 @@ pub struct Options {
 
     /// Set to true to handle conversion while it is being typed
     pub imemode: bool,
+
+    /// The romanization system used by to_romaji(). to_kana() reads the spellings of
+    /// [`Romanization::Nihon`] back when it is set. Ignored where [`Options::scheme`] replaces
+    /// the built-in tables.
+    /// to_romaji('しつじゃ', { romanization: Romanization::Kunrei })
+    /// => "situzya"
+    pub romanization: Romanization,
+
+    /// How to_romaji() renders long vowels
+    /// to_romaji('とうきょう', { longVowels: LongVowels::Macron })
+    /// => "tōkyō"
+    pub long_vowels: LongVowels,
+
+    /// Set to true to romanize the particles は, へ and を as wa, e and o with to_romaji(). The
+    /// kana is taken as a particle if it stands alone between spaces or punctuation, directly
+    /// follows kanji or katakana (本をよむ, ペンは), ends a hiragana word before a space,
+    /// punctuation or another script (ぼくは がくせい, これはペン), or is marked with a preceding
+    /// [`PARTICLE_MARKER`](crate::constants::PARTICLE_MARKER), which is dropped.
+    /// to_romaji('わたし は がくせい', { particles: true })
+    /// => "watashi wa gakusei"
+    pub particles: bool,
+
+    /// Separates each mora or syllable in the output of to_romaji()
+    /// to_romaji('とうきょう', { separator: Separator::Mora("-") })
+    /// => "to-u-kyo-u"
+    pub separator: Option<Separator>,
+
+    /// How to_romaji() sets ん apart from a following vowel or y
+    /// to_romaji('おんよみ', { syllabicN: SyllabicN::Hyphen })
+    /// => "on-yomi"
+    pub syllabic_n: SyllabicN,
+
+    /// How to_romaji() renders っ at the end of a word
+    /// to_romaji('あっ', { finalSokuon: FinalSokuon::Apostrophe })
+    /// => "a'"
+    pub final_sokuon: FinalSokuon,
+
+    /// The width of the katakana output by to_katakana() and by to_kana() for uppercase input
+    /// to_katakana('ガソリン', { katakanaWidth: KatakanaWidth::Half })
+    /// => "ｶﾞｿﾘﾝ"
+    pub katakana_width: KatakanaWidth,
+
+    /// Set to true to expand the iteration marks ゝ, ゞ, ヽ, ヾ, 々 and 〻 into the chars they
+    /// repeat with to_romaji(), to_hiragana() and to_katakana()
+    /// to_romaji('いすゞ', { expandIterationMarks: true })
+    /// => "isuzu"
+    pub expand_iteration_marks: bool,
+
+    /// Set to true to convert hentaigana to the modern hiragana they are read as with
+    /// to_romaji(), to_hiragana() and to_katakana()
+    /// to_romaji('𛂇𛂺𛁗', { normalizeHentaigana: true })
+    /// => "nihoso"
+    pub normalize_hentaigana: bool,
+
+    /// Set to true to read kana in historical orthography (rekishiteki kanazukai) with
+    /// to_romaji(), converting them to modern kana usage first
+    /// to_romaji('けふ', { historicalKana: true })
+    /// => "kyou"
+    pub historical_kana: bool,
+
+    /// Set to true to romanize the katakana extensions of Ainu (ㇰ, ㇷ゚, ト゚) and the spellings of
+    /// the Ryukyuan languages (クヮ, ッワ) with to_romaji()
+    /// to_romaji('アイヌ イタㇰ', { extendedKatakana: true })
+    /// => "ainu itak"
+    pub extended_katakana: bool,
+
+    /// Romaji to kana mappings that override or extend the ones of to_kana(), to_hiragana() and
+    /// to_katakana()
+    /// to_kana('wi', { customKanaMapping: CustomKanaMapping::new([("wi", "ゐ")]) })
+    /// => "ゐ"
+    pub custom_kana_mapping: Option<CustomKanaMapping>,
+
+    /// Kana to romaji mappings that override or extend the ones of to_romaji()
+    /// to_romaji('ふじ', { customRomajiMapping: CustomRomajiMapping::new([("じ", "zi")]) })
+    /// => "fuzi"
+    pub custom_romaji_mapping: Option<CustomRomajiMapping>,
+
+    /// A transliteration scheme replacing the built-in conversion tables it has a table for, e.g.
+    /// loaded from a file
+    /// to_romaji('し', { scheme: Scheme::from_toml('\[to_romaji\] し = "si"') })
+    /// => "si"
+    pub scheme: Option<Scheme>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// Romanization systems supported by to_romaji().
+pub enum Romanization {
+    /// [Hepburn romanization](https://en.wikipedia.org/wiki/Hepburn_romanization) (し → shi, つ →
+    /// tsu, じゃ → ja)
+    #[default]
+    Hepburn,
+    /// [Kunrei-shiki romanization](https://en.wikipedia.org/wiki/Kunrei-shiki_romanization) as
+    /// standardized in ISO 3602 (し → si, つ → tu, じゃ → zya, ふ → hu)
+    Kunrei,
+    /// [Nihon-shiki romanization](https://en.wikipedia.org/wiki/Nihon-shiki_romanization), which
+    /// keeps ぢ/づ/を/ゐ/ゑ distinct (ぢ → di, づ → du, を → wo, ぢゃ → dya). Small kana are
+    /// written with x where they don't combine with the preceding kana (ぁ → xa, ふぁ → huxa,
+    /// あっ → axtu). to_kana() accepts the same spellings, so the romaji converts back to the
+    /// original kana, except for the katakana long dash, which is written as the vowel it
+    /// lengthens, and ヷ, ヸ, ヹ and ヺ. Set [`Options::upcase_katakana`] to keep katakana
+    /// apart.
+    Nihon,
+    /// Hepburn as used in Japanese passports. ん is always written n and becomes m before b, m
+    /// and p (しんいち → shinichi, なんば → namba), を is written o and long vowels are omitted
+    /// (とうきょう → tokyo) unless [`LongVowels::Oh`] or another rendering is set.
+    Passport,
+    /// [Wāpuro romaji](https://en.wikipedia.org/wiki/W%C4%81puro_r%C5%8Dmaji), spelled the way
+    /// kana are typed into an IME. to_kana() converts the output back to the same kana for every
+    /// string of hiragana, katakana and 'ー', which is why katakana are always upper-cased, small
+    /// kana are written with x (ぁ → xa, っ → xtsu) and long dashes are kept (スーパー →
+    /// SU-PA-). Kana without a romaji spelling (ゕ, ヶ, ヷ) are passed through.
+    Wapuro,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// Renderings of long vowels in to_romaji().
+///
+/// A vowel is long when it is followed by the same vowel kana (ああ, うう, ええ, おお), by う
+/// after an o (おう), or by a katakana long dash (ー). いい is kept as ii as in modified
+/// Hepburn, unless it is written with ー (ビール → bīru). おう and おお both lengthen the o,
+/// so e.g. おもう becomes omō. Only [`LongVowels::Unchanged`] keeps them apart (とうり → touri,
+/// とおり → toori), all other renderings write them alike.
+pub enum LongVowels {
+    /// Keep the vowels as written: とうきょう → toukyou, おおさか → oosaka, スーパー → suupaa
+    #[default]
+    Unchanged,
+    /// Use macrons: とうきょう → tōkyō, スーパー → sūpā
+    Macron,
+    /// Use circumflexes: とうきょう → tôkyô, スーパー → sûpâ
+    Circumflex,
+    /// Double the vowel, also for おう: とうきょう → tookyoo, おおさか → oosaka, スーパー → suupaa
+    Doubled,
+    /// Write the vowel once: とうきょう → tokyo, スーパー → supa
+    Omitted,
+    /// Write a long o as oh and omit the other long vowels: とうきょう → tohkyoh, スーパー → supa
+    Oh,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Where to_romaji() inserts a separator string between kana.
+///
+/// Only romanized kana are separated, so spaces, punctuation and other chars are left as they are.
+/// Separated ん doesn't need an apostrophe before vowels (きんえん → ki-n-e-n).
+pub enum Separator {
+    /// Separate every mora, including っ and ん: とうきょう → to-u-kyo-u, かっぱ → ka-p-pa,
+    /// ほんだ → ho-n-da
+    Mora(String),
+    /// Separate syllables. っ, ん and long vowels belong to the preceding syllable: とうきょう →
+    /// tou-kyou, かっぱ → kap-pa, ほんだ → hon-da. Combine it with [`LongVowels::Macron`] for
+    /// tō-kyō.
+    Syllable(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Classes of chars converted by to_halfwidth() and to_fullwidth(). All of them are converted by
+/// default.
+pub struct WidthOptions {
+    /// Convert the Latin letters: ＡＢＣ ⇔ ABC
+    pub letters: bool,
+    /// Convert the digits: １２３ ⇔ 123
+    pub digits: bool,
+    /// Convert the punctuation and symbols of ASCII: ！？＃ ⇔ !?#
+    pub punctuation: bool,
+    /// Convert the space: '　' ⇔ ' '
+    pub space: bool,
+}
+
+impl Default for WidthOptions {
+    fn default() -> Self {
+        WidthOptions {
+            letters: true,
+            digits: true,
+            punctuation: true,
+            space: true,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// Widths of katakana output.
+pub enum KatakanaWidth {
+    /// Fullwidth katakana: ガソリン
+    #[default]
+    Full,
+    /// [Halfwidth katakana](https://en.wikipedia.org/wiki/Half-width_kana). Voiced kana are split
+    /// into the base kana and ﾞ or ﾟ, and ー and Japanese punctuation use their halfwidth forms:
+    /// ｶﾞｿﾘﾝ, ｽｰﾊﾟｰ｡ Katakana without a halfwidth form (ヮ, ヵ, ヶ, ヰ, ヱ) stay fullwidth.
+    Half,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// Renderings of ん before a vowel or y in to_romaji(), where it would otherwise be read as part
+/// of the next syllable (おんよみ, not およみ).
+pub enum SyllabicN {
+    /// Keep the spelling of the romanization system: n' in Hepburn, Kunrei-shiki, Nihon-shiki
+    /// and wāpuro romaji, a plain n in passport Hepburn
+    #[default]
+    Unchanged,
+    /// Use an apostrophe: おんよみ → on'yomi
+    Apostrophe,
+    /// Use a hyphen: おんよみ → on-yomi
+    Hyphen,
+    /// Double the n: おんよみ → onnyomi
+    Doubled,
+    /// Don't disambiguate: おんよみ → onyomi
+    Plain,
+}
+
+impl SyllabicN {
+    /// The chars written between the n and the following vowel or y, if the rendering sets one.
+    pub(crate) fn mark(self) -> Option<&'static str> {
+        match self {
+            SyllabicN::Unchanged => None,
+            SyllabicN::Apostrophe => Some("'"),
+            SyllabicN::Hyphen => Some("-"),
+            SyllabicN::Doubled => Some("n"),
+            SyllabicN::Plain => Some(""),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// Renderings of っ in to_romaji() when there is no following consonant to double, i.e. at the end
+/// of the input or before a char that isn't kana.
+pub enum FinalSokuon {
+    /// Keep the spelling of the romanization system: dropped in most systems (あっ → a), xtsu in
+    /// wāpuro romaji
+    #[default]
+    Unchanged,
+    /// Drop it: あっ → a
+    Omitted,
+    /// Use an apostrophe for the glottal stop: あっ → a'
+    Apostrophe,
+    /// Spell out the small tsu: あっ → axtsu
+    SmallTsu,
+}
+
+impl FinalSokuon {
+    /// The romaji of a word-final っ, if the rendering sets one.
+    pub(crate) fn romaji(self) -> Option<&'static str> {
+        match self {
+            FinalSokuon::Unchanged => None,
+            FinalSokuon::Omitted => Some(""),
+            FinalSokuon::Apostrophe => Some("'"),
+            FinalSokuon::SmallTsu => Some("xtsu"),
+        }
+    }
 }
@@ -0,0 +1,55 @@
+//! Romanizes names the way they are written in Japanese passports, see
+//! [`Romanization::Passport`].
+//!
+//! # Examples
+//! ```
+//! use wana_kana::passport::*;
+//! assert_eq!(to_passport_name("さとう", "たろう"), "SATO Taro");
+//! assert_eq!(to_passport_name("ほんま", "しんいち"), "HOMMA Shinichi");
+//! assert_eq!(to_passport_name_with_opt("おおの", "ユウコ", true), "OHNO Yuko");
+//! ```
+
+use crate::options::{LongVowels, Options, Romanization};
+use crate::to_romaji::to_romaji_with_opt;
+
+#[inline]
+/// Romanizes a name given as `family` and `given` name in kana to the passport form `FAMILY
+/// Given`.
+pub fn to_passport_name(family: &str, given: &str) -> String {
+    to_passport_name_with_opt(family, given, false)
+}
+
+/// Romanizes a name given as `family` and `given` name in kana to the passport form `FAMILY
+/// Given`.
+///
+/// * `oh_spelling` - write long o sounds (おう, おお) as oh instead of o
+///
+/// # Examples
+/// ```
+/// use wana_kana::passport::*;
+/// assert_eq!(to_passport_name_with_opt("かとう", "きょうこ", false), "KATO Kyoko");
+/// assert_eq!(to_passport_name_with_opt("かとう", "きょうこ", true), "KATOH Kyohko");
+/// ```
+pub fn to_passport_name_with_opt(family: &str, given: &str, oh_spelling: bool) -> String {
+    let options = Options {
+        romanization: Romanization::Passport,
+        long_vowels: if oh_spelling {
+            LongVowels::Oh
+        } else {
+            LongVowels::Omitted
+        },
+        ..Default::default()
+    };
+    let family = to_romaji_with_opt(family.trim(), options.clone()).to_uppercase();
+    let given = to_romaji_with_opt(given.trim(), options).to_lowercase();
+    let mut given_chars = given.chars();
+    let given = match given_chars.next() {
+        Some(first) => format!("{}{}", first.to_uppercase(), given_chars.as_str()),
+        None => given,
+    };
+    [family, given]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,323 @@
+//! Transliteration schemes defined at runtime, see [`Scheme`].
+
+#[cfg(feature = "schemes")]
+use std::collections::BTreeMap;
+#[cfg(feature = "schemes")]
+use std::fmt;
+
+use crate::custom_mapping::{CustomKanaMapping, CustomRomajiMapping, MappingNode};
+
+/// A complete transliteration scheme, replacing the built-in conversion tables of to_kana(),
+/// to_hiragana(), to_katakana() and to_romaji() when set in
+/// [`Options::scheme`](crate::Options::scheme).
+///
+/// Romaji are matched case insensitively and kana keys may be given as hiragana or katakana,
+/// which are mapped the same way. Like in the built-in tables the longest match wins, so
+/// sokuon and yōon need their own entries (っか → kka, きゃ → kya). Custom mappings are still
+/// applied on top of the scheme.
+///
+/// Each direction is replaced only if the scheme has a table for it. A scheme with an empty
+/// `to_kana` table leaves to_kana() to the built-in tables, one with an empty `to_romaji` table
+/// leaves to_romaji() to them. If the scheme has a `to_romaji` table, it takes precedence over
+/// [`Options::romanization`](crate::Options::romanization), which is then ignored by
+/// to_romaji(), and also by to_kana() if the scheme has a `to_kana` table.
+///
+/// With the `schemes` feature, schemes can be loaded from TOML or JSON files with the tables
+/// `to_kana`, `to_romaji` and `long_vowels`, all of them optional:
+///
+/// ```toml
+/// [to_kana]
+/// ka = "か"
+/// si = "し"
+///
+/// [to_romaji]
+/// "か" = "ka"
+/// "し" = "si"
+///
+/// [long_vowels]
+/// a = "â"
+/// o = "ô"
+/// ```
+///
+/// # Examples
+/// ```
+/// use wana_kana::{ConvertJapanese, CustomKanaMapping, CustomRomajiMapping, Options, Scheme};
+/// let scheme = Scheme {
+///     to_kana: CustomKanaMapping::new([("ka", "か"), ("si", "し")]),
+///     to_romaji: CustomRomajiMapping::new([("か", "ka"), ("し", "si"), ("く", "ku"), ("う", "u")]),
+///     long_vowels: vec![('u', "û".to_string())],
+/// };
+/// let options = Options {
+///     scheme: Some(scheme),
+///     ..Default::default()
+/// };
+/// assert_eq!("kasi shi".to_kana_with_opt(options.clone()), "かし shi");
+/// assert_eq!("シカ くうき".to_romaji_with_opt(options), "sika kûき");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Scheme {
+    /// Romaji to kana mappings used by to_kana(), to_hiragana() and to_katakana()
+    pub to_kana: CustomKanaMapping,
+    /// Kana to romaji mappings used by to_romaji()
+    pub to_romaji: CustomRomajiMapping,
+    /// Renderings of long vowels in to_romaji(), e.g. `('o', "ô")`. They apply like
+    /// [`Options::long_vowels`](crate::Options::long_vowels), which takes precedence if it is set.
+    pub long_vowels: Vec<(char, String)>,
+}
+
+impl Scheme {
+    /// The romaji to kana table, unless the scheme leaves to_kana() to the built-in tables.
+    pub(crate) fn kana_mapping(&self) -> Option<&MappingNode> {
+        Some(&*self.to_kana.0).filter(|mapping| !mapping.is_empty())
+    }
+
+    /// The kana to romaji table, unless the scheme leaves to_romaji() to the built-in tables.
+    pub(crate) fn romaji_mapping(&self) -> Option<&MappingNode> {
+        Some(&*self.to_romaji.0).filter(|mapping| !mapping.is_empty())
+    }
+
+    /// Returns the rendering of the long `vowel`, if the scheme has one.
+    pub(crate) fn long_vowel(&self, vowel: char) -> Option<&str> {
+        self.long_vowels
+            .iter()
+            .find(|(long_vowel, _)| *long_vowel == vowel)
+            .map(|(_, rendering)| rendering.as_str())
+    }
+}
+
+#[cfg(feature = "schemes")]
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SchemeFile {
+    #[serde(default)]
+    to_kana: BTreeMap<String, String>,
+    #[serde(default)]
+    to_romaji: BTreeMap<String, String>,
+    #[serde(default)]
+    long_vowels: BTreeMap<String, String>,
+}
+
+#[cfg(feature = "schemes")]
+impl TryFrom<SchemeFile> for Scheme {
+    type Error = SchemeError;
+
+    fn try_from(file: SchemeFile) -> Result<Self, Self::Error> {
+        let long_vowels = file
+            .long_vowels
+            .into_iter()
+            .map(|(vowel, rendering)| match vowel.as_str() {
+                "a" | "i" | "u" | "e" | "o" => Ok((vowel.chars().next().unwrap(), rendering)),
+                _ => Err(SchemeError::LongVowel(vowel)),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Scheme {
+            to_kana: file.to_kana.into_iter().collect(),
+            to_romaji: file.to_romaji.into_iter().collect(),
+            long_vowels,
+        })
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "schemes")))]
+#[cfg(feature = "schemes")]
+impl Scheme {
+    /// Loads a scheme from TOML.
+    ///
+    /// # Examples
+    /// ```
+    /// use wana_kana::{ConvertJapanese, Options, Scheme};
+    /// let scheme = Scheme::from_toml(r#"
+    ///     [to_kana]
+    ///     ti = "ち"
+    ///     [to_romaji]
+    ///     "ち" = "ti"
+    /// "#).unwrap();
+    /// let options = Options { scheme: Some(scheme), ..Default::default() };
+    /// assert_eq!("ti".to_kana_with_opt(options.clone()), "ち");
+    /// assert_eq!("チ".to_romaji_with_opt(options), "ti");
+    /// ```
+    pub fn from_toml(input: &str) -> Result<Scheme, SchemeError> {
+        toml::from_str::<SchemeFile>(input)
+            .map_err(SchemeError::Toml)?
+            .try_into()
+    }
+
+    /// Loads a scheme from JSON.
+    ///
+    /// # Examples
+    /// ```
+    /// use wana_kana::{ConvertJapanese, Options, Scheme};
+    /// let scheme = Scheme::from_json(r#"{ "to_romaji": { "ち": "ti" } }"#).unwrap();
+    /// let options = Options { scheme: Some(scheme), ..Default::default() };
+    /// assert_eq!("ちぢ".to_romaji_with_opt(options), "tiぢ");
+    /// ```
+    pub fn from_json(input: &str) -> Result<Scheme, SchemeError> {
+        serde_json::from_str::<SchemeFile>(input)
+            .map_err(SchemeError::Json)?
+            .try_into()
+    }
+}
+
+/// Errors loading a [`Scheme`].
+#[cfg_attr(docsrs, doc(cfg(feature = "schemes")))]
+#[cfg(feature = "schemes")]
+#[derive(Debug)]
+pub enum SchemeError {
+    /// The TOML is invalid or doesn't describe a scheme
+    Toml(toml::de::Error),
+    /// The JSON is invalid or doesn't describe a scheme
+    Json(serde_json::Error),
+    /// A long vowel rendering is given for something else than a, i, u, e or o
+    LongVowel(String),
+}
+
+#[cfg(feature = "schemes")]
+impl fmt::Display for SchemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemeError::Toml(err) => write!(f, "invalid scheme TOML: {err}"),
+            SchemeError::Json(err) => write!(f, "invalid scheme JSON: {err}"),
+            SchemeError::LongVowel(vowel) => {
+                write!(
+                    f,
+                    "long vowel rendering for {vowel:?}, which is not a vowel"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "schemes")]
+impl std::error::Error for SchemeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SchemeError::Toml(err) => Some(err),
+            SchemeError::Json(err) => Some(err),
+            SchemeError::LongVowel(_) => None,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "schemes"))]
+mod tests {
+    use super::*;
+    use crate::options::{LongVowels, Options, Romanization};
+    use crate::to_kana::to_kana_with_opt;
+    use crate::to_romaji::to_romaji_with_opt;
+
+    const SCHEME_TOML: &str = r#"
+        [to_kana]
+        a = "あ"
+        ka = "か"
+        kka = "っか"
+        to = "と"
+        u = "う"
+        "-" = "ー"
+
+        [to_romaji]
+        "あ" = "a"
+        "か" = "ka"
+        "っか" = "kka"
+        "と" = "to"
+        "う" = "u"
+        "ー" = "-"
+
+        [long_vowels]
+        o = "ô"
+    "#;
+
+    fn with_scheme(scheme: Scheme) -> Options {
+        Options {
+            scheme: Some(scheme),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn loads_toml() {
+        let options = with_scheme(Scheme::from_toml(SCHEME_TOML).unwrap());
+        assert_eq!(to_kana_with_opt("akka to", options.clone()), "あっか と");
+        assert_eq!(to_kana_with_opt("KA-", options.clone()), "カー");
+        assert_eq!(to_kana_with_opt("shi", options.clone()), "shi");
+        assert_eq!(to_romaji_with_opt("あっか", options.clone()), "akka");
+        assert_eq!(to_romaji_with_opt("とうか", options.clone()), "tôka");
+        assert_eq!(to_romaji_with_opt("し", options), "し");
+    }
+
+    #[test]
+    fn loads_json() {
+        let scheme = Scheme::from_json(
+            r#"{
+                "to_kana": { "ka": "か" },
+                "to_romaji": { "か": "ka", "あ": "a" },
+                "long_vowels": { "a": "aa" }
+            }"#,
+        )
+        .unwrap();
+        let options = with_scheme(scheme);
+        assert_eq!(to_kana_with_opt("kaka", options.clone()), "かか");
+        assert_eq!(to_romaji_with_opt("かあか", options), "kaaka");
+    }
+
+    #[test]
+    fn options_long_vowels_take_precedence() {
+        let options = Options {
+            long_vowels: LongVowels::Macron,
+            ..with_scheme(Scheme::from_toml(SCHEME_TOML).unwrap())
+        };
+        assert_eq!(to_romaji_with_opt("とう", options), "tō");
+    }
+
+    #[test]
+    fn custom_mappings_apply_on_top() {
+        let options = Options {
+            custom_romaji_mapping: Some([("か", "ca")].into_iter().collect()),
+            ..with_scheme(Scheme::from_toml(SCHEME_TOML).unwrap())
+        };
+        assert_eq!(to_romaji_with_opt("かっか", options), "cakka");
+    }
+
+    #[test]
+    fn falls_back_to_the_built_in_tables() {
+        let options = with_scheme(Scheme::from_toml("[to_romaji]\n\"し\" = \"si\"").unwrap());
+        assert_eq!(to_kana_with_opt("shi", options.clone()), "し");
+        assert_eq!(to_romaji_with_opt("しか", options), "siか");
+        let options = Options {
+            romanization: Romanization::Kunrei,
+            ..with_scheme(Scheme::from_toml("[to_kana]\nsi = \"し\"").unwrap())
+        };
+        assert_eq!(to_kana_with_opt("sika", options.clone()), "しka");
+        assert_eq!(to_romaji_with_opt("しつ", options), "situ");
+        let options = with_scheme(Scheme::from_toml("[long_vowels]\no = \"ô\"").unwrap());
+        assert_eq!(to_kana_with_opt("toukyou", options.clone()), "とうきょう");
+        assert_eq!(to_romaji_with_opt("とうきょう", options), "tôkyô");
+    }
+
+    #[test]
+    fn takes_precedence_over_romanization() {
+        let options = Options {
+            romanization: Romanization::Kunrei,
+            ..with_scheme(Scheme::from_toml(SCHEME_TOML).unwrap())
+        };
+        assert_eq!(to_romaji_with_opt("しか", options), "しka");
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert!(matches!(
+            Scheme::from_toml("[to_kana]\nka = 1"),
+            Err(SchemeError::Toml(_))
+        ));
+        assert!(matches!(
+            Scheme::from_toml("[to_kanji]"),
+            Err(SchemeError::Toml(_))
+        ));
+        assert!(matches!(Scheme::from_json("{"), Err(SchemeError::Json(_))));
+        let err = Scheme::from_toml("[long_vowels]\nn = \"nn\"").unwrap_err();
+        assert!(matches!(err, SchemeError::LongVowel(ref vowel) if vowel == "n"));
+        assert_eq!(
+            err.to_string(),
+            "long vowel rendering for \"n\", which is not a vowel"
+        );
+    }
+}
@@ -1,7 +1,13 @@
+use std::borrow::Cow;
+
 use crate::is_mixed::*;
 use crate::is_romaji::*;
 use crate::options::Options;
+use crate::utils::expand_iteration_marks::*;
+use crate::utils::halfwidth_katakana_to_katakana::*;
+use crate::utils::hentaigana_to_hiragana::*;
 use crate::utils::is_char_english_punctuation::is_char_english_punctuation;
+use crate::utils::is_char_zenkaku_letter::*;
 use crate::utils::katakana_to_hiragana::*;
 use crate::utils::romaji_to_hiragana::romaji_to_hiragana;
 
@@ -13,10 +19,21 @@ pub fn to_hiragana(input: &str) -> String {
 
 /// Convert input to [Hiragana](https://en.wikipedia.org/wiki/Hiragana)
 pub fn to_hiragana_with_opt(input: &str, options: Options) -> String {
+    let input = &*halfwidth_katakana_to_katakana(input);
+    let input = &*if options.normalize_hentaigana {
+        hentaigana_to_hiragana(input)
+    } else {
+        Cow::Borrowed(input)
+    };
+    let input = &*if options.expand_iteration_marks {
+        expand_iteration_marks(input)
+    } else {
+        Cow::Borrowed(input)
+    };
     let config = options;
     if config.pass_romaji {
         katakana_to_hiragana(input)
-    } else if is_mixed(input) {
+    } else if is_mixed(input) || input.chars().any(is_char_zenkaku_letter) {
         let romaji = katakana_to_hiragana(input);
         romaji_to_hiragana(&romaji, config)
     } else if is_romaji(input)
@@ -49,6 +66,25 @@ mod tests {
     fn sane_defaults() {
         assert_eq!(to_hiragana(""), "");
     }
+    #[test]
+    fn normalizes_hentaigana() {
+        let options = Options {
+            normalize_hentaigana: true,
+            ..Default::default()
+        };
+        assert_eq!(to_hiragana_with_opt("𛂇𛂺ン", options), "にほん");
+        assert_eq!(to_hiragana("𛂇𛂺"), "𛂇𛂺");
+    }
+    #[test]
+    fn expands_iteration_marks() {
+        let options = Options {
+            expand_iteration_marks: true,
+            ..Default::default()
+        };
+        assert_eq!(to_hiragana_with_opt("バナヽ", options.clone()), "ばなな");
+        assert_eq!(to_hiragana_with_opt("時々", options), "時時");
+        assert_eq!(to_hiragana("バナヽ"), "ばなヽ");
+    }
 
     #[test]
     fn quick_brown_fox_romaji_to_hiragana() {
@@ -153,6 +189,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn zenkaku_romaji() {
+        assert_eq!(to_hiragana("ｋｏｎｎｉｃｈｉｈａ"), "こんにちは");
+        assert_eq!(
+            to_hiragana("ＴＯＵＫＹＯＵ、オオサカ"),
+            "とうきょう、おおさか"
+        );
+    }
+
+    #[test]
+    fn halfwidth_katakana() {
+        assert_eq!(to_hiragana("ｶﾞｿﾘﾝ"), "がそりん");
+        assert_eq!(to_hiragana("ｶﾞｿﾘﾝ gasu"), "がそりん がす");
+    }
+
     #[test]
     fn mixed_input() {
         assert_eq!(
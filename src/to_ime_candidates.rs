@@ -0,0 +1,104 @@
+use crate::options::{KatakanaWidth, Options};
+use crate::to_hiragana::to_hiragana_with_opt;
+use crate::to_katakana::to_katakana_with_opt;
+use crate::to_width::{to_fullwidth, to_halfwidth};
+
+/// The forms an IME offers for the romaji being typed, usually bound to F6 through F10.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ImeCandidates {
+    /// Hiragana (F6): きょう
+    pub hiragana: String,
+    /// Fullwidth katakana (F7): キョウ
+    pub katakana: String,
+    /// Halfwidth katakana (F8): ｷｮｳ
+    pub halfwidth_katakana: String,
+    /// Fullwidth romaji (F9): ｋｙｏｕ
+    pub fullwidth_romaji: String,
+    /// Halfwidth romaji (F10): kyou
+    pub halfwidth_romaji: String,
+}
+
+/// Convert romaji to all forms an IME offers while typing
+pub fn to_ime_candidates(input: &str) -> ImeCandidates {
+    to_ime_candidates_with_opt(input, Options::default())
+}
+
+/// Convert romaji to all forms an IME offers while typing. The options apply to the kana forms,
+/// the romaji forms keep the input as typed.
+pub fn to_ime_candidates_with_opt(input: &str, options: Options) -> ImeCandidates {
+    ImeCandidates {
+        hiragana: to_hiragana_with_opt(input, options.clone()),
+        katakana: to_katakana_with_opt(
+            input,
+            Options {
+                katakana_width: KatakanaWidth::Full,
+                ..options.clone()
+            },
+        ),
+        halfwidth_katakana: to_katakana_with_opt(
+            input,
+            Options {
+                katakana_width: KatakanaWidth::Half,
+                ..options
+            },
+        ),
+        fullwidth_romaji: to_fullwidth(input),
+        halfwidth_romaji: to_halfwidth(input),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sane_defaults() {
+        assert_eq!(to_ime_candidates(""), ImeCandidates::default());
+    }
+
+    #[test]
+    fn returns_all_forms() {
+        assert_eq!(
+            to_ime_candidates("toukyou"),
+            ImeCandidates {
+                hiragana: "とうきょう".to_string(),
+                katakana: "トウキョウ".to_string(),
+                halfwidth_katakana: "ﾄｳｷｮｳ".to_string(),
+                fullwidth_romaji: "ｔｏｕｋｙｏｕ".to_string(),
+                halfwidth_romaji: "toukyou".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn keeps_the_romaji_as_typed() {
+        let candidates = to_ime_candidates("Pa-thi-!");
+        assert_eq!(candidates.halfwidth_katakana, "ﾊﾟｰﾃｨｰ!");
+        assert_eq!(candidates.fullwidth_romaji, "Ｐａ－ｔｈｉ－！");
+        assert_eq!(to_ime_candidates("ｓｈｉ").halfwidth_romaji, "shi");
+    }
+
+    #[test]
+    fn halfwidth_katakana_has_halfwidth_punctuation() {
+        let candidates = to_ime_candidates("(pa-thi-?)");
+        assert_eq!(candidates.katakana, "（パーティー？）");
+        assert_eq!(candidates.halfwidth_katakana, "(ﾊﾟｰﾃｨｰ?)");
+        assert_eq!(to_ime_candidates("[ka]!").halfwidth_katakana, "[ｶ]!");
+    }
+
+    #[test]
+    fn uses_the_options() {
+        let candidates = to_ime_candidates_with_opt(
+            "wi",
+            Options {
+                use_obsolete_kana: true,
+                katakana_width: KatakanaWidth::Half,
+                ..Default::default()
+            },
+        );
+        assert_eq!(candidates.hiragana, "ゐ");
+        assert_eq!(candidates.katakana, "ヰ");
+        // there is no halfwidth ヰ
+        assert_eq!(candidates.halfwidth_katakana, "ヰ");
+    }
+}
@@ -1,8 +1,72 @@
-use crate::options::Options;
-use crate::to_kana_node_tree::{
-    TO_KANA_NODE_TREE, TO_KANA_NODE_TREE_IMEMODE, TO_KANA_NODE_TREE_OBSOLETE,
-};
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::custom_mapping::MappingNode;
+use crate::options::{KatakanaWidth, Options, Romanization};
+use crate::to_kana_node_tree::{Node, TreeVariant, EMPTY_NODE_TREE};
+use crate::utils::halfwidth_katakana_to_katakana::*;
 use crate::utils::hiragana_to_katakana::*;
+use crate::utils::is_char_zenkaku_letter::*;
+use crate::utils::katakana_to_halfwidth_katakana::*;
+
+/// The conversion tables selected by the options
+struct Tables<'a> {
+    tree: &'static Node,
+    /// The scheme's table, if it replaces the tree
+    scheme: Option<Cow<'a, MappingNode>>,
+    custom: Option<&'a MappingNode>,
+}
+
+impl<'a> Tables<'a> {
+    fn new(options: &'a Options) -> Self {
+        let variant = TreeVariant {
+            obsolete_kana: options.use_obsolete_kana,
+            nihon: options.romanization == Romanization::Nihon,
+            imemode: options.imemode,
+        };
+        let scheme = options
+            .scheme
+            .as_ref()
+            .and_then(|scheme| scheme.kana_mapping());
+        Tables {
+            tree: match scheme {
+                Some(_) => &EMPTY_NODE_TREE,
+                None => variant.tree(),
+            },
+            scheme: scheme.map(|mapping| variant.apply(mapping)),
+            custom: options
+                .custom_kana_mapping
+                .as_ref()
+                .map(|custom| &*custom.0),
+        }
+    }
+
+    fn custom_mappings(&self) -> [Option<&MappingNode>; 2] {
+        [self.scheme.as_deref(), self.custom]
+    }
+
+    fn get(&self, chars: &[char]) -> (Option<&str>, usize) {
+        match self.custom_mappings() {
+            [None, None] => self.tree.get(chars),
+            custom => self.tree.get_with_custom(custom, chars),
+        }
+    }
+
+    fn has_longer_match(&self, chars: &[char]) -> bool {
+        self.tree.has_longer_match(self.custom_mappings(), chars)
+    }
+}
+
+/// Number of romaji chars at the start of `chars` that are converted together, 0 if the first
+/// char is passed through.
+pub(crate) fn match_len(chars: &[char], options: &Options) -> usize {
+    Tables::new(options).get(chars).1
+}
+
+/// Whether more romaji could still turn `chars` into a longer match, e.g. `ky` into `kya`.
+pub(crate) fn is_incomplete(chars: &[char], options: &Options) -> bool {
+    Tables::new(options).has_longer_match(chars)
+}
 
 #[inline]
 /// Convert [Romaji](https://en.wikipedia.org/wiki/Romaji) to [Kana](https://en.wikipedia.org/wiki/Kana), lowercase text will result in [Hiragana](https://en.wikipedia.org/wiki/Hiragana) and uppercase text will result in [Katakana](https://en.wikipedia.org/wiki/Katakana).
@@ -12,42 +76,207 @@ pub fn to_kana(input: &str) -> String {
 
 /// Convert [Romaji](https://en.wikipedia.org/wiki/Romaji) to [Kana](https://en.wikipedia.org/wiki/Kana), lowercase text will result in [Hiragana](https://en.wikipedia.org/wiki/Hiragana) and uppercase text will result in [Katakana](https://en.wikipedia.org/wiki/Katakana).
 pub fn to_kana_with_opt(input: &str, options: Options) -> String {
-    let chars = input.chars().collect::<Vec<_>>();
+    convert(input, &options, None)
+}
+
+/// Convert [Romaji](https://en.wikipedia.org/wiki/Romaji) to [Kana](https://en.wikipedia.org/wiki/Kana) like [`to_kana`], failing if any romaji letters can't be converted.
+pub fn to_kana_strict(input: &str) -> Result<String, UnconvertedRomaji> {
+    to_kana_strict_with_opt(input, Options::default())
+}
+
+/// Convert [Romaji](https://en.wikipedia.org/wiki/Romaji) to [Kana](https://en.wikipedia.org/wiki/Kana) like [`to_kana_with_opt`], failing if any romaji letters can't be converted.
+pub fn to_kana_strict_with_opt(input: &str, options: Options) -> Result<String, UnconvertedRomaji> {
+    let mut spans = Vec::new();
+    let output = convert(input, &options, Some(&mut spans));
+    if spans.is_empty() {
+        return Ok(output);
+    }
+    // The spans are found in the input with halfwidth voicing marks merged
+    let offsets = halfwidth_katakana_to_katakana_offsets(input);
+    let input_chars = input.chars().collect::<Vec<_>>();
+    let spans = spans
+        .into_iter()
+        .map(|(start, end, reason)| {
+            let start = offsets[start];
+            let end = offsets.get(end).copied().unwrap_or(input_chars.len());
+            UnconvertedSpan {
+                start,
+                end,
+                text: input_chars[start..end].iter().collect(),
+                reason,
+            }
+        })
+        .collect();
+    Err(UnconvertedRomaji { output, spans })
+}
+
+/// Converts `input`, collecting the spans of romaji letters which are passed through into
+/// `unconverted`, as char offsets in the input after halfwidth katakana normalization.
+fn convert(
+    input: &str,
+    options: &Options,
+    mut unconverted: Option<&mut Vec<(usize, usize, UnconvertedReason)>>,
+) -> String {
+    let input = &*halfwidth_katakana_to_katakana(input);
+    let tables = Tables::new(options);
+    let orig_chars = input.chars().collect::<Vec<_>>();
+    // Zenkaku letters, punctuation and spaces are read like their ASCII counterparts
+    let chars = orig_chars
+        .iter()
+        .map(|char| zenkaku_to_ascii(*char))
+        .collect::<Vec<_>>();
     // Final output array
     let mut ouput = String::with_capacity(input.len());
     let len = chars.len();
     // Position in the string that is being evaluated
     let mut curr_pos = 0;
+    // Whether the previous match was converted to katakana
+    let mut follows_katakana = false;
+    // Start of the romaji letters passed through up to the current position
+    let mut unconverted_start = None;
 
     while curr_pos != len {
-        let result = if options.use_obsolete_kana {
-            TO_KANA_NODE_TREE_OBSOLETE.get(&chars[curr_pos..])
-        } else if options.imemode {
-            TO_KANA_NODE_TREE_IMEMODE.get(&chars[curr_pos..])
-        } else {
-            TO_KANA_NODE_TREE.get(&chars[curr_pos..])
-        };
-
-        // nothing found, pass through
-        if result.1 == 0 || result.0.is_none() {
-            ouput.push(chars[curr_pos]);
-            curr_pos += 1;
-        } else {
-            if chars[curr_pos..curr_pos + result.1]
-                .iter()
-                .all(|c| char::is_uppercase(*c))
-            {
-                ouput.push_str(&hiragana_to_katakana(result.0.unwrap()));
-            } else {
-                ouput.push_str(result.0.unwrap());
+        let result = tables.get(&chars[curr_pos..]);
+        if let Some(unconverted) = &mut unconverted {
+            let is_unconverted = result.1 == 0 && chars[curr_pos].is_ascii_alphabetic();
+            match unconverted_start {
+                None if is_unconverted => unconverted_start = Some(curr_pos),
+                Some(start) if !is_unconverted => {
+                    unconverted.push(unconverted_span(&chars, start, curr_pos, &tables));
+                    unconverted_start = None;
+                }
+                _ => {}
+            }
+        }
+        match result {
+            (Some(kana), len) if len != 0 => {
+                let romaji = &chars[curr_pos..curr_pos + len];
+                // the apostrophe in n' has no case
+                let is_katakana = romaji
+                    .iter()
+                    .filter(|c| **c != '\'')
+                    .all(|c| char::is_uppercase(*c));
+                let kana = if is_katakana {
+                    Cow::Owned(hiragana_to_katakana(kana))
+                } else {
+                    Cow::Borrowed(kana)
+                };
+                // punctuation and long dashes have no case and follow the preceding katakana
+                let is_caseless = !romaji.iter().any(|c| c.is_alphabetic());
+                follows_katakana = is_katakana || (follows_katakana && is_caseless);
+                if options.katakana_width == KatakanaWidth::Half && follows_katakana {
+                    ouput.push_str(&katakana_to_halfwidth_katakana(&kana));
+                } else {
+                    ouput.push_str(&kana);
+                }
+                curr_pos += len;
+            }
+            // nothing found, pass through
+            _ => {
+                ouput.push(orig_chars[curr_pos]);
+                curr_pos += 1;
+                follows_katakana = false;
             }
-            curr_pos += result.1;
         }
     }
+    if let (Some(unconverted), Some(start)) = (unconverted, unconverted_start) {
+        unconverted.push(unconverted_span(&chars, start, len, &tables));
+    }
 
     ouput
 }
 
+fn unconverted_span(
+    chars: &[char],
+    start: usize,
+    end: usize,
+    tables: &Tables,
+) -> (usize, usize, UnconvertedReason) {
+    // more letters could have completed the romaji, e.g. a vowel after ky
+    let reason = if tables.has_longer_match(&chars[start..end]) {
+        UnconvertedReason::DanglingConsonant
+    } else {
+        UnconvertedReason::UnknownSequence
+    };
+    (start, end, reason)
+}
+
+/// Why romaji couldn't be converted to kana, see [`UnconvertedSpan`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnconvertedReason {
+    /// The romaji are the start of a kana, but the rest is missing, e.g. `ky` without a vowel
+    DanglingConsonant,
+    /// No kana is spelled like this, e.g. `kxq`
+    UnknownSequence,
+}
+
+impl fmt::Display for UnconvertedReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnconvertedReason::DanglingConsonant => write!(f, "dangling consonant"),
+            UnconvertedReason::UnknownSequence => write!(f, "unknown sequence"),
+        }
+    }
+}
+
+/// Romaji letters that [`to_kana_strict`](crate::ConvertJapanese::to_kana_strict) couldn't convert.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnconvertedSpan {
+    /// Offset of the first letter in the input, in chars
+    pub start: usize,
+    /// Offset after the last letter in the input, in chars
+    pub end: usize,
+    /// The letters
+    pub text: String,
+    /// Why the letters couldn't be converted
+    pub reason: UnconvertedReason,
+}
+
+/// The error of [`to_kana_strict`](crate::ConvertJapanese::to_kana_strict), listing the romaji
+/// that couldn't be converted.
+///
+/// # Examples
+/// ```
+/// use wana_kana::{ConvertJapanese, UnconvertedReason};
+/// let err = "kxq desu ky".to_kana_strict().unwrap_err();
+/// assert_eq!(err.output, "kxq です ky");
+/// assert_eq!(err.spans[0].text, "kxq");
+/// assert_eq!(err.spans[0].reason, UnconvertedReason::UnknownSequence);
+/// assert_eq!((err.spans[1].start, err.spans[1].end), (9, 11));
+/// assert_eq!(err.spans[1].reason, UnconvertedReason::DanglingConsonant);
+/// assert_eq!(
+///     err.to_string(),
+///     "could not convert \"kxq\" at 0..3 (unknown sequence), \"ky\" at 9..11 (dangling consonant)"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnconvertedRomaji {
+    /// The conversion with the unconverted letters passed through, like
+    /// [`to_kana`](crate::ConvertJapanese::to_kana) returns it
+    pub output: String,
+    /// The unconverted letters, in the order of the input
+    pub spans: Vec<UnconvertedSpan>,
+}
+
+impl fmt::Display for UnconvertedRomaji {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "could not convert ")?;
+        for (i, span) in self.spans.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(
+                f,
+                "{:?} at {}..{} ({})",
+                span.text, span.start, span.end, span.reason
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for UnconvertedRomaji {}
+
 #[cfg(test)]
 mod tests {
     fn with_obsolete_kana() -> Options {
@@ -85,6 +314,177 @@ mod tests {
     fn uppercase_ist_katakana_defaults() {
         assert_eq!(to_kana("WANAKANA"), "ワナカナ");
     }
+    #[test]
+    fn halfwidth_output_for_uppercase() {
+        let with_halfwidth = Options {
+            katakana_width: KatakanaWidth::Half,
+            ..Default::default()
+        };
+        assert_eq!(
+            to_kana_with_opt("GASORIN to SU-PA-.", with_halfwidth),
+            "ｶﾞｿﾘﾝ と ｽｰﾊﾟｰ｡"
+        );
+    }
+    mod custom_kana_mapping {
+        use super::*;
+        use crate::custom_mapping::CustomKanaMapping;
+
+        fn with_mapping(mapping: &[(&str, &str)]) -> Options {
+            Options {
+                custom_kana_mapping: Some(mapping.iter().copied().collect()),
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn overrides_mappings() {
+            let options = with_mapping(&[("wi", "ゐ"), ("la", "ら")]);
+            assert_eq!(to_kana_with_opt("wiwa", options.clone()), "ゐわ");
+            assert_eq!(to_kana_with_opt("lali", options.clone()), "らぃ");
+            assert_eq!(to_kana_with_opt("LALI", options), "ラィ");
+        }
+        #[test]
+        fn adds_mappings() {
+            let options = with_mapping(&[("xyz", "えっくす"), ("@", "あっと")]);
+            assert_eq!(
+                to_kana_with_opt("xyza@", options.clone()),
+                "えっくすああっと"
+            );
+            assert_eq!(to_kana_with_opt("q", options), "q");
+        }
+        #[test]
+        fn keeps_the_longest_match() {
+            let options = with_mapping(&[("la", "ら"), ("ts", "つ")]);
+            assert_eq!(to_kana_with_opt("lalya", options.clone()), "らゃ");
+            assert_eq!(to_kana_with_opt("tsu", options.clone()), "つ");
+            assert_eq!(to_kana_with_opt("tsa", options), "つぁ");
+        }
+        #[test]
+        fn is_merged_into_the_selected_tree() {
+            let options = Options {
+                use_obsolete_kana: true,
+                ..with_mapping(&[("la", "ら")])
+            };
+            assert_eq!(to_kana_with_opt("wela", options), "ゑら");
+        }
+        #[test]
+        fn used_by_to_hiragana_and_to_katakana() {
+            let options = with_mapping(&[("wi", "ゐ")]);
+            assert_eq!(
+                crate::to_hiragana::to_hiragana_with_opt("wi", options.clone()),
+                "ゐ"
+            );
+            assert_eq!(
+                crate::to_katakana::to_katakana_with_opt("wi", options),
+                "ヰ"
+            );
+        }
+        #[test]
+        fn empty_mapping() {
+            assert_eq!(to_kana_with_opt("wi", with_mapping(&[])), to_kana("wi"));
+            assert_eq!(
+                CustomKanaMapping::new([("", "あ")]),
+                CustomKanaMapping::default()
+            );
+        }
+    }
+    mod strict {
+        use super::*;
+
+        fn span(
+            start: usize,
+            end: usize,
+            text: &str,
+            reason: UnconvertedReason,
+        ) -> UnconvertedSpan {
+            UnconvertedSpan {
+                start,
+                end,
+                text: text.to_string(),
+                reason,
+            }
+        }
+
+        #[test]
+        fn converts_like_to_kana() {
+            assert_eq!(to_kana_strict(""), Ok(String::new()));
+            assert_eq!(
+                to_kana_strict("onaji BUTTSUUJI"),
+                Ok("おなじ ブッツウジ".to_string())
+            );
+            assert_eq!(
+                to_kana_strict("座禅‘zazen’ 123"),
+                Ok("座禅「ざぜん」 123".to_string())
+            );
+        }
+        #[test]
+        fn reports_unconverted_romaji() {
+            assert_eq!(
+                to_kana_strict("kxqa kak"),
+                Err(UnconvertedRomaji {
+                    output: "kxくぁ かk".to_string(),
+                    spans: vec![
+                        span(0, 2, "kx", UnconvertedReason::UnknownSequence),
+                        span(7, 8, "k", UnconvertedReason::DanglingConsonant),
+                    ],
+                })
+            );
+            assert_eq!(
+                to_kana_strict("TQ").unwrap_err().spans,
+                [span(0, 2, "TQ", UnconvertedReason::UnknownSequence)]
+            );
+        }
+        #[test]
+        fn offsets_in_the_input() {
+            assert_eq!(
+                to_kana_strict("ｶﾞｿﾘﾝ ky").unwrap_err().spans,
+                [span(6, 8, "ky", UnconvertedReason::DanglingConsonant)]
+            );
+            assert_eq!(
+                to_kana_strict("かんじ ｋｙ").unwrap_err().spans,
+                [span(4, 6, "ｋｙ", UnconvertedReason::DanglingConsonant)]
+            );
+        }
+        #[test]
+        fn uses_the_options() {
+            assert_eq!(
+                to_kana_strict_with_opt("shin", with_ime_mode())
+                    .unwrap_err()
+                    .spans,
+                [span(3, 4, "n", UnconvertedReason::DanglingConsonant)]
+            );
+            assert!(to_kana_strict_with_opt("we", with_obsolete_kana()).is_ok());
+        }
+    }
+
+    #[test]
+    fn zenkaku_romaji() {
+        assert_eq!(to_kana("ｋｏｎｎｉｃｈｉｈａ"), "こんにちは");
+        assert_eq!(to_kana("ＷＡＮＡＫＡＮＡ"), "ワナカナ");
+        assert_eq!(
+            to_kana("ｏｎａｊｉ ＢＵＴＴＳＵＵＪＩ"),
+            "おなじ ブッツウジ"
+        );
+        assert_eq!(to_kana("ｋonnichiｈａ"), "こんにちは");
+        assert_eq!(to_kana("ｑｑ"), "ｑｑ");
+        assert_eq!(to_kana("ｋｏｎ＇ｙａ"), to_kana("kon'ya"));
+        assert_eq!(to_kana("ｓｕ－ｐａ－！"), "すーぱー！");
+        assert_eq!(to_kana("ｋａ　ｋｉ"), "か　き");
+        assert_eq!(
+            to_kana_with_opt(
+                "ｎ　",
+                Options {
+                    imemode: true,
+                    ..Default::default()
+                }
+            ),
+            "ん"
+        );
+    }
+    #[test]
+    fn halfwidth_katakana_becomes_fullwidth() {
+        assert_eq!(to_kana("ｶﾞｿﾘﾝ sutando"), "ガソリン すたんど");
+    }
 
     #[test]
     fn lowercase_characters_are_transliterated_to_hiragana() {
@@ -156,10 +556,18 @@ mod tests {
         fn double_ns_are_transliterated_to_double_ん() {
             assert_eq!(to_kana("nn"), "んん");
         }
+        #[test]
+        fn uppercase_n_with_apostrophe_is_katakana() {
+            assert_eq!(to_kana("N'A"), "ンア");
+            assert_eq!(to_kana("n'A"), "んア");
+        }
     }
 
     mod with_ime_mode {
         use super::*;
+        use crate::composer::Composer;
+        use crate::custom_mapping::CustomKanaMapping;
+        use crate::scheme::Scheme;
         #[test]
         fn solo_ns_are_not_transliterated_unless_chars_follow() {
             assert_eq!(to_kana_with_opt("n", with_ime_mode()), "n");
@@ -172,6 +580,45 @@ mod tests {
         fn double_ns_are_transliterated_to_singleん() {
             assert_eq!(to_kana_with_opt("nn", with_ime_mode()), "ん");
         }
+        #[test]
+        fn combines_with_other_options() {
+            let options = Options {
+                use_obsolete_kana: true,
+                ..with_ime_mode()
+            };
+            assert_eq!(to_kana_with_opt("win", options.clone()), "ゐn");
+            assert_eq!(to_kana_with_opt("wenn", options), "ゑん");
+            let options = Options {
+                romanization: Romanization::Nihon,
+                ..with_ime_mode()
+            };
+            assert_eq!(to_kana_with_opt("kwan", options.clone()), "くゎn");
+            assert_eq!(to_kana_with_opt("wi kwan ", options), "ゐ くゎん");
+        }
+        #[test]
+        fn combines_with_a_scheme_and_custom_mappings() {
+            let scheme = Scheme {
+                to_kana: CustomKanaMapping::new([("n", "ん"), ("na", "な"), ("wi", "うぃ")]),
+                ..Default::default()
+            };
+            let options = Options {
+                scheme: Some(scheme),
+                custom_kana_mapping: Some(CustomKanaMapping::new([("la", "ら")])),
+                ..with_ime_mode()
+            };
+            assert_eq!(to_kana_with_opt("n", options.clone()), "n");
+            assert_eq!(to_kana_with_opt("nnna n ", options.clone()), "んな ん");
+            assert_eq!(to_kana_with_opt("lawi", options.clone()), "らうぃ");
+            let options = Options {
+                use_obsolete_kana: true,
+                ..options
+            };
+            assert_eq!(to_kana_with_opt("wina", options.clone()), "ゐな");
+            assert_eq!(to_kana_with_opt("wen", options.clone()), "ゑn");
+            let mut composer = Composer::with_opt(options);
+            composer.push_str("nan");
+            assert_eq!(composer.commit(), "なん");
+        }
     }
 
     mod use_obsolete_kana {
@@ -186,6 +633,11 @@ mod tests {
             assert_eq!(to_kana_with_opt("wi", with_obsolete_kana()), "ゐ");
         }
         #[test]
+        fn wyi_and_wye_are_always_obsolete_kana() {
+            assert_eq!(to_kana("wyi wye"), "ゐ ゑ");
+            assert_eq!(to_kana("WYI WYE"), "ヰ ヱ");
+        }
+        #[test]
         fn we_ゑ_when_use_obsolete_kana_is_true() {
             assert_eq!(to_kana_with_opt("we", with_obsolete_kana()), "ゑ");
         }
@@ -198,4 +650,27 @@ mod tests {
             assert_eq!(to_kana_with_opt("WE", with_obsolete_kana()), "ヱ");
         }
     }
+
+    mod nihon {
+        use super::*;
+
+        fn with_nihon() -> Options {
+            Options {
+                romanization: Romanization::Nihon,
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn accepts_nihon_spellings() {
+            assert_eq!(to_kana_with_opt("diduwo", with_nihon()), "ぢづを");
+            assert_eq!(to_kana_with_opt("wiwe", with_nihon()), "ゐゑ");
+            assert_eq!(to_kana_with_opt("kwagwa", with_nihon()), "くゎぐゎ");
+            assert_eq!(to_kana_with_opt("KWAGWA", with_nihon()), "クヮグヮ");
+        }
+        #[test]
+        fn kwa_is_くぁ_by_default() {
+            assert_eq!(to_kana("kwa"), "くぁ");
+        }
+    }
 }
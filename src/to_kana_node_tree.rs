@@ -1,3 +1,8 @@
+use std::borrow::Cow;
+use std::sync::OnceLock;
+
+use crate::custom_mapping::MappingNode;
+
 #[derive(Debug, Clone)]
 pub(crate) struct Node {
     pub transitions: Vec<(char, Node)>,
@@ -24,6 +29,62 @@ impl Node {
         }
     }
 
+    /// Like [`Node::get`], on the tree with the `custom` mappings merged in. Custom outputs take
+    /// precedence, the last mappings first.
+    pub(crate) fn get_with_custom<'a, const N: usize>(
+        &'a self,
+        mut custom: [Option<&'a MappingNode>; N],
+        chars: &[char],
+    ) -> (Option<&'a str>, usize) {
+        let mut i = 0;
+        let mut curr_node = Some(self);
+        for char in chars.iter() {
+            let char = char.to_ascii_lowercase();
+            let trans_node = curr_node.and_then(|node| node.find_transition_node(char));
+            let custom_trans_nodes =
+                custom.map(|node| node.and_then(|node| node.find_transition_node(char)));
+            if trans_node.is_none() && custom_trans_nodes.iter().all(Option::is_none) {
+                break;
+            }
+            curr_node = trans_node;
+            custom = custom_trans_nodes;
+            i += 1;
+        }
+
+        let output = custom
+            .iter()
+            .rev()
+            .find_map(|node| node.and_then(|node| node.output.as_deref()))
+            .or(curr_node.and_then(|node| node.output));
+        if output.is_some() {
+            (output, i)
+        } else {
+            (None, 0)
+        }
+    }
+
+    /// Whether a longer mapping starts with `chars`, with the `custom` mappings merged in.
+    pub(crate) fn has_longer_match<const N: usize>(
+        &self,
+        mut custom: [Option<&MappingNode>; N],
+        chars: &[char],
+    ) -> bool {
+        let mut curr_node = Some(self);
+        for char in chars.iter() {
+            let char = char.to_ascii_lowercase();
+            curr_node = curr_node.and_then(|node| node.find_transition_node(char));
+            custom = custom.map(|node| node.and_then(|node| node.find_transition_node(char)));
+            if curr_node.is_none() && custom.iter().all(Option::is_none) {
+                return false;
+            }
+        }
+        curr_node.is_some_and(|node| !node.transitions.is_empty())
+            || custom
+                .iter()
+                .flatten()
+                .any(|node| !node.transitions.is_empty())
+    }
+
     pub(crate) fn find_transition_node(&self, char: char) -> Option<&Node> {
         self.transitions.iter().find(|&t| t.0 == char).map(|t| &t.1)
         // self.transitions.binary_search_by_key(&char, |t|
@@ -34,6 +95,29 @@ impl Node {
         self.transitions.iter_mut().find(|t| t.0 == char)
     }
 
+    /// Sets the output of the node reached by `romaji`, appending the final node if it doesn't
+    /// exist yet. The tree has to be sorted afterwards.
+    fn set_output(&mut self, romaji: &str, output: &'static str) {
+        let mut curr_node = self;
+        for char in romaji.chars() {
+            let index = match curr_node.transitions.iter().position(|t| t.0 == char) {
+                Some(index) => index,
+                None => {
+                    curr_node.transitions.push((
+                        char,
+                        Node {
+                            transitions: vec![],
+                            output: None,
+                        },
+                    ));
+                    curr_node.transitions.len() - 1
+                }
+            };
+            curr_node = &mut curr_node.transitions[index].1;
+        }
+        curr_node.output = Some(output);
+    }
+
     fn sort(&mut self) {
         self.transitions.sort_by_key(|el| el.0);
         for el in &mut self.transitions {
@@ -42,6 +126,13 @@ impl Node {
     }
 }
 
+/// The tree without any transitions, which custom mappings are merged into when they replace the
+/// built-in trees
+pub(crate) static EMPTY_NODE_TREE: Node = Node {
+    transitions: Vec::new(),
+    output: None,
+};
+
 #[test]
 fn test_node_tree() {
     let chars = ['a'];
@@ -4813,49 +4904,115 @@ lazy_static! {
             transitions,
             output: None,
         };
+        // Spellings of the obsolete kana used by IMEs, which keep wi and we as うぃ and うぇ
+        tree.set_output("wyi", "ゐ");
+        tree.set_output("wye", "ゑ");
         tree.sort();
         tree
     };
-    pub(crate) static ref TO_KANA_NODE_TREE_OBSOLETE: Node = {
-        let mut tree = TO_KANA_NODE_TREE.clone();
-        let w = tree.find_transition_mut('w').unwrap();
-        w.1.transitions.retain(|x| x.0 != 'i' && x.0 != 'e');
-        w.1.transitions.push((
-            'i',
-            Node {
-                transitions: vec![],
-                output: Some("ゐ"),
-            },
-        ));
-        w.1.transitions.push((
-            'e',
-            Node {
-                transitions: vec![],
-                output: Some("ゑ"),
-            },
-        ));
-        tree.sort();
-        tree
-    };
-    pub(crate) static ref TO_KANA_NODE_TREE_IMEMODE: Node = {
-        let mut tree = TO_KANA_NODE_TREE.clone();
-        let w = tree.find_transition_mut('n').unwrap();
-        w.1.transitions.push((
-            'n',
-            Node {
-                transitions: vec![],
-                output: Some("ん"),
-            },
-        ));
-        w.1.transitions.push((
-            ' ',
-            Node {
-                transitions: vec![],
-                output: Some("ん"),
-            },
-        ));
-        w.1.output = None;
-        tree.sort();
-        tree
-    };
+}
+
+/// The behaviours of to_kana() which change the tree. Each one is applied on top of the others,
+/// so every combination of them gets its own tree.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TreeVariant {
+    /// wi and we are ゐ and ゑ
+    pub obsolete_kana: bool,
+    /// The Nihon-shiki spellings kwa and gwa, along with the obsolete kana
+    pub nihon: bool,
+    /// n on its own isn't converted while it is being typed, nn and n followed by a space are ん
+    pub imemode: bool,
+}
+
+impl TreeVariant {
+    /// Returns the tree with all behaviours of the variant, building it on first use.
+    pub(crate) fn tree(self) -> &'static Node {
+        static TREES: [OnceLock<Node>; 8] = [const { OnceLock::new() }; 8];
+        let index =
+            self.obsolete_kana as usize | (self.nihon as usize) << 1 | (self.imemode as usize) << 2;
+        if index == 0 {
+            return &TO_KANA_NODE_TREE;
+        }
+        TREES[index].get_or_init(|| {
+            let mut tree = TO_KANA_NODE_TREE.clone();
+            if self.obsolete_kana || self.nihon {
+                add_obsolete_kana(&mut tree);
+            }
+            if self.nihon {
+                add_nihon_spellings(&mut tree);
+            }
+            if self.imemode {
+                add_imemode(&mut tree);
+            }
+            tree.sort();
+            tree
+        })
+    }
+
+    /// Returns the table of a scheme replacing the tree with the behaviours of the variant applied
+    /// to it. The Nihon-shiki spellings are left to the scheme.
+    pub(crate) fn apply(self, mapping: &MappingNode) -> Cow<'_, MappingNode> {
+        if !self.obsolete_kana && !self.imemode {
+            return Cow::Borrowed(mapping);
+        }
+        let mut mapping = mapping.clone();
+        if self.obsolete_kana {
+            mapping.insert("wi", "ゐ".to_string());
+            mapping.insert("we", "ゑ".to_string());
+        }
+        if self.imemode {
+            if let Some(n) = mapping.take_output("n") {
+                mapping.insert("nn", n.clone());
+                mapping.insert("n ", n);
+            }
+        }
+        Cow::Owned(mapping)
+    }
+}
+
+fn add_obsolete_kana(tree: &mut Node) {
+    let w = tree.find_transition_mut('w').unwrap();
+    w.1.transitions.retain(|x| x.0 != 'i' && x.0 != 'e');
+    w.1.transitions.push((
+        'i',
+        Node {
+            transitions: vec![],
+            output: Some("ゐ"),
+        },
+    ));
+    w.1.transitions.push((
+        'e',
+        Node {
+            transitions: vec![],
+            output: Some("ゑ"),
+        },
+    ));
+}
+
+fn add_nihon_spellings(tree: &mut Node) {
+    tree.set_output("wwi", "っゐ");
+    tree.set_output("wwe", "っゑ");
+    tree.set_output("kwa", "くゎ");
+    tree.set_output("kkwa", "っくゎ");
+    tree.set_output("gwa", "ぐゎ");
+    tree.set_output("ggwa", "っぐゎ");
+}
+
+fn add_imemode(tree: &mut Node) {
+    let n = tree.find_transition_mut('n').unwrap();
+    n.1.transitions.push((
+        'n',
+        Node {
+            transitions: vec![],
+            output: Some("ん"),
+        },
+    ));
+    n.1.transitions.push((
+        ' ',
+        Node {
+            transitions: vec![],
+            output: Some("ん"),
+        },
+    ));
+    n.1.output = None;
 }
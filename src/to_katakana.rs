@@ -1,7 +1,14 @@
+use std::borrow::Cow;
+
 use crate::is_mixed::*;
 use crate::is_romaji::*;
-use crate::options::Options;
+use crate::options::{KatakanaWidth, Options};
+use crate::utils::expand_iteration_marks::*;
+use crate::utils::halfwidth_katakana_to_katakana::*;
+use crate::utils::hentaigana_to_hiragana::*;
 use crate::utils::hiragana_to_katakana::*;
+use crate::utils::is_char_zenkaku_letter::*;
+use crate::utils::katakana_to_halfwidth_katakana::*;
 use crate::utils::romaji_to_hiragana::*;
 
 /// Convert input to [Katakana](https://en.wikipedia.org/wiki/Katakana)
@@ -10,14 +17,30 @@ pub fn to_katakana(input: &str) -> String {
 }
 /// Convert input to [Katakana](https://en.wikipedia.org/wiki/Katakana)
 pub fn to_katakana_with_opt(input: &str, options: Options) -> String {
+    let input = &*halfwidth_katakana_to_katakana(input);
+    let input = &*if options.normalize_hentaigana {
+        hentaigana_to_hiragana(input)
+    } else {
+        Cow::Borrowed(input)
+    };
+    let input = &*if options.expand_iteration_marks {
+        expand_iteration_marks(input)
+    } else {
+        Cow::Borrowed(input)
+    };
     let config = options;
-    if config.pass_romaji {
+    let katakana_width = config.katakana_width;
+    let katakana = if config.pass_romaji {
         hiragana_to_katakana(input)
-    } else if is_romaji(input) || is_mixed(input) {
+    } else if is_romaji(input) || is_mixed(input) || input.chars().any(is_char_zenkaku_letter) {
         let romaji = romaji_to_hiragana(input, config);
         hiragana_to_katakana(&romaji)
     } else {
         hiragana_to_katakana(input)
+    };
+    match katakana_width {
+        KatakanaWidth::Full => katakana,
+        KatakanaWidth::Half => katakana_to_halfwidth_katakana(&katakana),
     }
 }
 
@@ -36,6 +59,56 @@ mod tests {
     fn sane_defaults() {
         assert_eq!(to_katakana(""), "");
     }
+    #[test]
+    fn normalizes_hentaigana() {
+        let options = Options {
+            normalize_hentaigana: true,
+            ..Default::default()
+        };
+        assert_eq!(to_katakana_with_opt("𛂇𛂺ん", options), "ニホン");
+        assert_eq!(to_katakana("𛂇𛂺"), "𛂇𛂺");
+    }
+    #[test]
+    fn expands_iteration_marks() {
+        let options = Options {
+            expand_iteration_marks: true,
+            ..Default::default()
+        };
+        assert_eq!(to_katakana_with_opt("いすゞ", options), "イスズ");
+        assert_eq!(to_katakana("いすゞ"), "イスゞ");
+    }
+
+    #[test]
+    fn halfwidth_output() {
+        let with_halfwidth = || Options {
+            katakana_width: KatakanaWidth::Half,
+            ..Default::default()
+        };
+        assert_eq!(to_katakana_with_opt("ガソリン", with_halfwidth()), "ｶﾞｿﾘﾝ");
+        assert_eq!(to_katakana_with_opt("がそりん", with_halfwidth()), "ｶﾞｿﾘﾝ");
+        assert_eq!(to_katakana_with_opt("gasorin", with_halfwidth()), "ｶﾞｿﾘﾝ");
+        assert_eq!(
+            to_katakana_with_opt("パーティー。", with_halfwidth()),
+            "ﾊﾟｰﾃｨｰ｡"
+        );
+        assert_eq!(to_katakana_with_opt("pa-thi-!", with_halfwidth()), "ﾊﾟｰﾃｨｰ!");
+        assert_eq!(
+            to_katakana_with_opt("（パーティー？）", with_halfwidth()),
+            "(ﾊﾟｰﾃｨｰ?)"
+        );
+        assert_eq!(to_katakana_with_opt("ｶﾞｿﾘﾝ", with_halfwidth()), "ｶﾞｿﾘﾝ");
+    }
+
+    #[test]
+    fn zenkaku_romaji() {
+        assert_eq!(to_katakana("ｋｏｎｎｉｃｈｉｈａ"), "コンニチハ");
+    }
+
+    #[test]
+    fn halfwidth_katakana() {
+        assert_eq!(to_katakana("ｶﾞｿﾘﾝ"), "ガソリン");
+        assert_eq!(to_katakana("ｶﾞｿﾘﾝ の ねだん"), "ガソリン ノ ネダン");
+    }
 
     #[test]
     fn quick_brown_fox_romaji_to_katakana() {
@@ -1,8 +1,128 @@
-use crate::options::Options;
+use std::borrow::Cow;
+
+use crate::constants::PARTICLE_MARKER;
+use crate::options::{LongVowels, Options, Romanization, Separator};
 pub(crate) use crate::to_romaji_node_tree::TO_ROMAJI_NODE_TREE;
+use crate::to_romaji_node_tree::{
+    EMPTY_NODE_TREE, EXTENDED_KATAKANA_MAPPING, TO_ROMAJI_NODE_TREE_KUNREI,
+    TO_ROMAJI_NODE_TREE_NIHON, TO_ROMAJI_NODE_TREE_PASSPORT, TO_ROMAJI_NODE_TREE_WAPURO,
+};
+use crate::utils::expand_iteration_marks::expand_iteration_marks;
+use crate::utils::halfwidth_katakana_to_katakana::{
+    halfwidth_katakana_to_katakana, halfwidth_katakana_to_katakana_offsets,
+};
+use crate::utils::hentaigana_to_hiragana::hentaigana_to_hiragana;
+use crate::utils::historical_kana_to_modern_kana::historical_kana_to_modern_kana_with_offsets;
+use crate::utils::is_char_hiragana::is_char_hiragana;
+use crate::utils::is_char_kana::is_char_kana;
+use crate::utils::is_char_kanji::is_char_kanji;
 use crate::utils::is_char_katakana::is_char_katakana;
+use crate::utils::is_char_long_dash::is_char_long_dash;
+use crate::utils::is_char_punctuation::is_char_punctuation;
+use crate::utils::is_char_romaji::is_char_romaji;
 use crate::utils::katakana_to_hiragana::*;
 
+/// Returns true if `kana` lengthens the preceding romaji `vowel`. `orig` is the char `kana` was
+/// converted from, to detect the katakana long dash.
+fn is_long_vowel(vowel: char, kana: char, orig: char) -> bool {
+    if is_char_long_dash(orig) {
+        // a long dash which could not be resolved is passed through as 'ー'
+        return !is_char_long_dash(kana);
+    }
+    matches!(
+        (vowel, kana),
+        ('a', 'あ') | ('u', 'う') | ('e', 'え') | ('o', 'う') | ('o', 'お')
+    )
+}
+
+/// Returns the romaji of the particle at `pos` and the number of chars it spans, if the kana there
+/// is used as a particle.
+fn get_particle(chars: &[char], pos: usize) -> Option<(&'static str, usize)> {
+    let to_romaji = |kana: char| match kana {
+        'は' => Some("wa"),
+        'へ' => Some("e"),
+        'を' => Some("o"),
+        _ => None,
+    };
+    if chars[pos] == PARTICLE_MARKER {
+        return chars
+            .get(pos + 1)
+            .and_then(|kana| to_romaji(*kana))
+            .map(|romaji| (romaji, 2));
+    }
+    let romaji = to_romaji(chars[pos])?;
+    let prev = pos.checked_sub(1).map(|prev| chars[prev]);
+    let next = chars.get(pos + 1).copied();
+    let is_boundary = |char: Option<char>| char.is_none_or(is_char_punctuation);
+    let stands_alone = is_boundary(prev) && is_boundary(next);
+    // The kana is split off a kanji or katakana word by the change of script (本をよむ, ペンは)
+    let follows_word = prev.is_some_and(|prev| is_char_kanji(prev) || is_char_katakana(prev));
+    // A hiragana word ends with it before a space, punctuation or another script (ぼくは がくせい,
+    // これはペン). At the end of the input it is more likely part of the word (はは).
+    let ends_word =
+        prev.is_some_and(is_char_hiragana) && next.is_some_and(|c| !is_char_hiragana(c));
+    (stands_alone || follows_word || ends_word).then_some((romaji, 1))
+}
+
+/// Splits the romaji of a match starting with っ or ん into the part for っ or ん and the rest,
+/// e.g. "ppa" into "p" and "pa", "n'a" into "n" and "a". Returns the length of the first part and
+/// the number of chars to skip before the rest, which is the length of the mark after ん.
+fn split_coda(kana: &[char], romaji: &str, syllabic_n_mark_len: usize) -> (usize, usize) {
+    let mut romaji_chars = romaji.chars();
+    let first = romaji_chars.next();
+    let second = romaji_chars.next();
+    let is_doubled = match (first, second) {
+        (Some(first), Some(second)) => {
+            first.eq_ignore_ascii_case(&second)
+                || (first.eq_ignore_ascii_case(&'t') && second.eq_ignore_ascii_case(&'c'))
+        }
+        _ => false,
+    };
+    match kana[0] {
+        'っ' if is_doubled => (1, 0),
+        'ん' if kana.len() > 1 && romaji.len() > 1 => (1, syllabic_n_mark_len),
+        _ => (0, 0),
+    }
+}
+
+/// Returns the end of the run of katakana or non-katakana chars starting at `start`.
+fn script_end(chars: &[char], start: usize) -> usize {
+    let Some(first) = chars.get(start) else {
+        return start;
+    };
+    let is_katakana = is_char_katakana(*first);
+    chars[start..]
+        .iter()
+        .position(|c| is_char_katakana(*c) != is_katakana)
+        .map_or(chars.len(), |pos| start + pos)
+}
+
+fn lengthen_vowel(vowel: char, long_vowels: LongVowels) -> &'static str {
+    match (long_vowels, vowel) {
+        (LongVowels::Macron, 'a') => "ā",
+        (LongVowels::Macron, 'i') => "ī",
+        (LongVowels::Macron, 'u') => "ū",
+        (LongVowels::Macron, 'e') => "ē",
+        (LongVowels::Macron, 'o') => "ō",
+        (LongVowels::Circumflex, 'a') => "â",
+        (LongVowels::Circumflex, 'i') => "î",
+        (LongVowels::Circumflex, 'u') => "û",
+        (LongVowels::Circumflex, 'e') => "ê",
+        (LongVowels::Circumflex, 'o') => "ô",
+        (LongVowels::Oh, 'o') => "oh",
+        (LongVowels::Omitted | LongVowels::Oh, 'a') => "a",
+        (LongVowels::Omitted | LongVowels::Oh, 'i') => "i",
+        (LongVowels::Omitted | LongVowels::Oh, 'u') => "u",
+        (LongVowels::Omitted | LongVowels::Oh, 'e') => "e",
+        (LongVowels::Omitted, 'o') => "o",
+        (_, 'a') => "aa",
+        (_, 'i') => "ii",
+        (_, 'u') => "uu",
+        (_, 'e') => "ee",
+        _ => "oo",
+    }
+}
+
 /// Convert kana to romaji
 pub fn to_romaji(input: &str) -> String {
     to_romaji_with_opt(input, Options::default())
@@ -10,38 +130,343 @@ pub fn to_romaji(input: &str) -> String {
 
 /// Convert kana to romaji
 pub fn to_romaji_with_opt(orig: &str, options: Options) -> String {
-    let kana = katakana_to_hiragana_with_opt(orig, true);
+    convert(orig, &options, None)
+}
+
+/// Convert kana to romaji, reporting the spans of the input that were passed through without
+/// being romanized, e.g. kanji to look up the reading of.
+///
+/// Spans of chars which are romaji already, like latin letters, digits, ASCII punctuation and
+/// spaces, are not reported.
+pub fn to_romaji_with_report(input: &str, options: Options) -> RomajiReport {
+    let mut positions = Vec::new();
+    let output = convert(input, &options, Some(&mut positions));
+    // The positions are in the input with halfwidth voicing marks merged
+    let offsets = halfwidth_katakana_to_katakana_offsets(input);
+    let input_chars = input.chars().collect::<Vec<_>>();
+    let mut passthrough: Vec<PassthroughSpan> = Vec::new();
+    let mut prev_position = None;
+    for position in positions {
+        // chars converted from the same historical kana
+        if prev_position == Some(position) {
+            continue;
+        }
+        let start = offsets[position];
+        let end = offsets
+            .get(position + 1)
+            .copied()
+            .unwrap_or(input_chars.len());
+        let category = PassthroughCategory::of(input_chars[start]);
+        match passthrough.last_mut() {
+            Some(span)
+                if prev_position.is_some_and(|prev| prev + 1 == position)
+                    && span.category == category =>
+            {
+                span.end = end;
+                span.text.extend(&input_chars[start..end]);
+            }
+            _ => passthrough.push(PassthroughSpan {
+                start,
+                end,
+                text: input_chars[start..end].iter().collect(),
+                category,
+            }),
+        }
+        prev_position = Some(position);
+    }
+    RomajiReport {
+        output,
+        passthrough,
+    }
+}
+
+/// The result of [`to_romaji_with_report`](crate::ConvertJapanese::to_romaji_with_report).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RomajiReport {
+    /// The romaji, like [`to_romaji_with_opt`](crate::ConvertJapanese::to_romaji_with_opt)
+    /// returns them
+    pub output: String,
+    /// The spans of the input which were passed through without being romanized
+    pub passthrough: Vec<PassthroughSpan>,
+}
+
+/// Chars [`to_romaji_with_report`](crate::ConvertJapanese::to_romaji_with_report) passed through
+/// without romanizing them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PassthroughSpan {
+    /// Offset of the first char in the input, in chars
+    pub start: usize,
+    /// Offset after the last char in the input, in chars
+    pub end: usize,
+    /// The chars
+    pub text: String,
+    /// What kind of chars they are
+    pub category: PassthroughCategory,
+}
+
+/// The kind of chars in a [`PassthroughSpan`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassthroughCategory {
+    /// Kanji, which need a reading to be romanized
+    Kanji,
+    /// Kana without a romanization, e.g. ヶ or ヵ
+    Kana,
+    /// Anything else, e.g. Japanese symbols
+    Other,
+}
+
+impl PassthroughCategory {
+    fn of(char: char) -> Self {
+        if is_char_kanji(char) {
+            PassthroughCategory::Kanji
+        } else if is_char_kana(char) {
+            PassthroughCategory::Kana
+        } else {
+            PassthroughCategory::Other
+        }
+    }
+}
+
+/// Converts `orig`, collecting the positions of the chars which are passed through without being
+/// romanized into `passthrough`, in chars of the input after halfwidth katakana normalization
+/// (and iteration mark expansion, which keeps the positions).
+fn convert(orig: &str, options: &Options, mut passthrough: Option<&mut Vec<usize>>) -> String {
+    let orig = &*halfwidth_katakana_to_katakana(orig);
+    let orig = &*if options.normalize_hentaigana {
+        hentaigana_to_hiragana(orig)
+    } else {
+        Cow::Borrowed(orig)
+    };
+    let orig = &*if options.expand_iteration_marks {
+        expand_iteration_marks(orig)
+    } else {
+        Cow::Borrowed(orig)
+    };
+    // Offsets of the modern kana in the historical ones
+    let mut historical_offsets = Vec::new();
+    let orig = &*if options.historical_kana {
+        Cow::Owned(historical_kana_to_modern_kana_with_offsets(
+            orig,
+            &mut historical_offsets,
+        ))
+    } else {
+        Cow::Borrowed(orig)
+    };
+    // A scheme replaces the built-in tables, along with their special rules
+    let scheme_mapping = options
+        .scheme
+        .as_ref()
+        .and_then(|scheme| scheme.romaji_mapping());
+    let romanization = match scheme_mapping {
+        Some(_) => Romanization::Hepburn,
+        None => options.romanization,
+    };
+    let reversible = romanization == Romanization::Wapuro;
+    let kana = katakana_to_hiragana_with_opt(orig, true, !reversible);
     let orig_chars = orig.chars().collect::<Vec<_>>();
-    let chars = kana.chars().collect::<Vec<_>>();
+    let mut chars = kana.chars().collect::<Vec<_>>();
+    if reversible {
+        // ヷ, ヸ, ヹ and ヺ would lose their voicing mark in hiragana
+        for (kana, orig) in chars.iter_mut().zip(&orig_chars) {
+            if ('ヷ'..='ヺ').contains(orig) {
+                *kana = *orig;
+            }
+        }
+    }
     let mut ouput = String::with_capacity(orig.len());
+    let tree = match romanization {
+        _ if scheme_mapping.is_some() => &EMPTY_NODE_TREE,
+        Romanization::Hepburn => &*TO_ROMAJI_NODE_TREE,
+        Romanization::Kunrei => &*TO_ROMAJI_NODE_TREE_KUNREI,
+        Romanization::Nihon => &*TO_ROMAJI_NODE_TREE_NIHON,
+        Romanization::Passport => &*TO_ROMAJI_NODE_TREE_PASSPORT,
+        Romanization::Wapuro => &*TO_ROMAJI_NODE_TREE_WAPURO,
+    };
+    let custom = [
+        options
+            .extended_katakana
+            .then(|| &*EXTENDED_KATAKANA_MAPPING.0),
+        scheme_mapping,
+        options
+            .custom_romaji_mapping
+            .as_ref()
+            .map(|custom| &*custom.0),
+    ];
+    let get = |chars: &[char]| {
+        if custom.iter().all(Option::is_none) {
+            tree.get(chars)
+        } else {
+            tree.get_with_custom(custom, chars)
+        }
+    };
+    let long_vowels = match (romanization, options.long_vowels) {
+        (Romanization::Passport, LongVowels::Unchanged) => LongVowels::Omitted,
+        (_, long_vowels) => long_vowels,
+    };
     let len = chars.len();
     // Position in the string that is being evaluated
     let mut curr_pos = 0;
+    // Last romaji char of the previous match, if it was kana. Separators go between two of them.
+    let mut prev_kana_end: Option<char> = None;
 
     while curr_pos != len {
-        let result = TO_ROMAJI_NODE_TREE.get(&chars[curr_pos..]);
+        if options.particles {
+            if let Some((romaji, particle_len)) = get_particle(&orig_chars, curr_pos) {
+                if let (
+                    Some(Separator::Mora(separator) | Separator::Syllable(separator)),
+                    Some(_),
+                ) = (&options.separator, prev_kana_end)
+                {
+                    ouput.push_str(separator);
+                }
+                ouput.push_str(romaji);
+                prev_kana_end = romaji.chars().last();
+                curr_pos += particle_len;
+                continue;
+            }
+        }
+        // Katakana are told apart by the case of the romaji, so in reversible romaji a match must
+        // not mix hiragana and katakana
+        let end = if reversible {
+            script_end(&orig_chars, curr_pos)
+        } else {
+            len
+        };
+        let result = get(&chars[curr_pos..end]);
         // nothing found, pass through
         if result.1 == 0 {
+            if let Some(passthrough) = &mut passthrough {
+                if !is_char_romaji(orig_chars[curr_pos]) {
+                    passthrough.push(if options.historical_kana {
+                        historical_offsets[curr_pos]
+                    } else {
+                        curr_pos
+                    });
+                }
+            }
             ouput.push(chars[curr_pos]);
             curr_pos += 1;
+            prev_kana_end = None;
         } else {
+            let mut romaji = Cow::Borrowed(result.0);
+            let mut mora_end = curr_pos + result.1;
+            if mora_end != len {
+                if let Some(vowel) = result.0.chars().last() {
+                    let lengthened = match long_vowels {
+                        LongVowels::Unchanged => options
+                            .scheme
+                            .as_ref()
+                            .and_then(|scheme| scheme.long_vowel(vowel)),
+                        _ => Some(lengthen_vowel(vowel, long_vowels)),
+                    };
+                    if let Some(lengthened) = lengthened {
+                        if is_long_vowel(vowel, chars[mora_end], orig_chars[mora_end]) {
+                            let short = &result.0[..result.0.len() - vowel.len_utf8()];
+                            romaji = Cow::Owned(short.to_string() + lengthened);
+                            mora_end += 1;
+                        }
+                    }
+                }
+            }
+            // Passport Hepburn writes ん as m before b, m and p
+            if romanization == Romanization::Passport
+                && romaji == "n"
+                && get(&chars[mora_end..]).0.starts_with(['b', 'm', 'p'])
+            {
+                romaji = Cow::Borrowed("m");
+            }
+            // A match ends before katakana following hiragana (or vice versa), in which case ん
+            // still needs the apostrophe
+            if reversible
+                && romaji == "n"
+                && get(&chars[mora_end..script_end(&orig_chars, mora_end)])
+                    .0
+                    .starts_with(['a', 'i', 'u', 'e', 'o', 'y'])
+            {
+                romaji = Cow::Borrowed("n'");
+            }
+            // Length of the mark setting ん apart from a following vowel or y
+            let mut syllabic_n_mark_len = 0;
+            if chars[curr_pos] == 'ん' && romaji.starts_with('n') {
+                let rest = romaji[1..].trim_start_matches('\'');
+                syllabic_n_mark_len = romaji.len() - 1 - rest.len();
+                if let Some(mark) = options.syllabic_n.mark() {
+                    if rest.starts_with(['a', 'i', 'u', 'e', 'o', 'y']) {
+                        romaji = Cow::Owned(format!("n{mark}{rest}"));
+                        syllabic_n_mark_len = mark.len();
+                    }
+                }
+            }
+            if let Some(final_sokuon) = options.final_sokuon.romaji() {
+                // っ is matched together with punctuation following it
+                if chars[curr_pos] == 'っ'
+                    && orig_chars
+                        .get(curr_pos + 1)
+                        .is_none_or(|c| !is_char_kana(*c))
+                {
+                    let rest = get(&chars[curr_pos + 1..mora_end]).0;
+                    romaji = Cow::Owned(format!("{final_sokuon}{rest}"));
+                }
+            }
+
             let convert_romaji_to_uppercase = {
-                if orig_chars[curr_pos..curr_pos + result.1]
+                if orig_chars[curr_pos..mora_end]
                     .iter()
                     .all(|c| is_char_katakana(*c))
                 {
-                    options.upcase_katakana
+                    options.upcase_katakana || reversible
                 } else {
                     false
                 }
             };
 
-            if convert_romaji_to_uppercase {
-                ouput.push_str(&result.0.to_uppercase());
+            let romaji_out = if convert_romaji_to_uppercase {
+                Cow::Owned(romaji.to_uppercase())
             } else {
-                ouput.push_str(result.0);
+                Cow::Borrowed(romaji.as_ref())
+            };
+            let is_kana = orig_chars[curr_pos..mora_end]
+                .iter()
+                .all(|c| is_char_kana(*c))
+                && romaji.starts_with(|c: char| c.is_ascii_alphabetic());
+            match &options.separator {
+                Some(separator) if is_kana => {
+                    let (is_syllable, separator) = match separator {
+                        Separator::Mora(separator) => (false, separator),
+                        Separator::Syllable(separator) => (true, separator),
+                    };
+                    let (coda_len, skip) =
+                        split_coda(&chars[curr_pos..mora_end], &romaji_out, syllabic_n_mark_len);
+                    // Without a preceding syllable っ and ん stay attached to the following kana
+                    let (coda_len, skip) = if is_syllable && prev_kana_end.is_none() {
+                        (0, 0)
+                    } else {
+                        (coda_len, skip)
+                    };
+                    let (coda, rest) = (&romaji_out[..coda_len], &romaji_out[coda_len + skip..]);
+                    if !coda.is_empty() {
+                        if !is_syllable && prev_kana_end.is_some() {
+                            ouput.push_str(separator);
+                        }
+                        ouput.push_str(coda);
+                    }
+                    // ん, っ and long vowels on their own belong to the preceding syllable
+                    let is_coda = chars[curr_pos] == 'ん'
+                        || chars[curr_pos] == 'っ'
+                        || prev_kana_end.is_some_and(|vowel| {
+                            is_long_vowel(vowel, chars[curr_pos], orig_chars[curr_pos])
+                        });
+                    if (prev_kana_end.is_some() || !coda.is_empty())
+                        && !(is_syllable && is_coda && coda.is_empty())
+                    {
+                        ouput.push_str(separator);
+                    }
+                    ouput.push_str(rest);
+                }
+                _ => ouput.push_str(&romaji_out),
             }
-            curr_pos += result.1;
+            prev_kana_end = if is_kana { romaji.chars().last() } else { None };
+            curr_pos = mora_end;
         }
     }
 
@@ -51,10 +476,134 @@ pub fn to_romaji_with_opt(orig: &str, options: Options) -> String {
 #[cfg(test)]
 mod tests {
     use super::{Options, *};
+    use crate::options::{FinalSokuon, SyllabicN};
+    mod report {
+        use super::*;
+
+        fn span(
+            start: usize,
+            end: usize,
+            text: &str,
+            category: PassthroughCategory,
+        ) -> PassthroughSpan {
+            PassthroughSpan {
+                start,
+                end,
+                text: text.to_string(),
+                category,
+            }
+        }
+
+        #[test]
+        fn reports_passthrough_spans() {
+            assert_eq!(
+                to_romaji_with_report("一抹げーむ、ヵ所と〆切", Options::default()),
+                RomajiReport {
+                    output: "一抹ge-mu,ヵ所to〆切".to_string(),
+                    passthrough: vec![
+                        span(0, 2, "一抹", PassthroughCategory::Kanji),
+                        span(6, 7, "ヵ", PassthroughCategory::Kana),
+                        span(7, 8, "所", PassthroughCategory::Kanji),
+                        span(9, 10, "〆", PassthroughCategory::Other),
+                        span(10, 11, "切", PassthroughCategory::Kanji),
+                    ],
+                }
+            );
+        }
+        #[test]
+        fn ignores_romaji() {
+            let report = to_romaji_with_report("ABC 123 ひらがな!", Options::default());
+            assert_eq!(report.output, "ABC 123 hiragana!");
+            assert!(report.passthrough.is_empty());
+        }
+        #[test]
+        fn offsets_in_the_input() {
+            assert_eq!(
+                to_romaji_with_report("ｶﾞｿﾘﾝ代", Options::default()).passthrough,
+                [span(5, 6, "代", PassthroughCategory::Kanji)]
+            );
+        }
+        #[test]
+        fn uses_the_options() {
+            let options = Options {
+                custom_romaji_mapping: Some([("ヶ", "ka")].into_iter().collect()),
+                ..Default::default()
+            };
+            let report = to_romaji_with_report("三ヶ月", options);
+            assert_eq!(report.output, "三ka月");
+            assert_eq!(report.passthrough.len(), 2);
+        }
+    }
     #[test]
     fn sane_defaults() {
         assert_eq!(to_romaji(""), "");
     }
+    #[test]
+    fn normalizes_hentaigana() {
+        let options = Options {
+            normalize_hentaigana: true,
+            ..Default::default()
+        };
+        assert_eq!(to_romaji_with_opt("𛂇𛂺ん", options), "nihon");
+        assert_eq!(to_romaji("𛂇𛂺"), "𛂇𛂺");
+        assert_eq!(
+            to_romaji_with_report("𛂇", Options::default()).passthrough[0].category,
+            PassthroughCategory::Kana
+        );
+    }
+    #[test]
+    fn expands_iteration_marks() {
+        let options = Options {
+            expand_iteration_marks: true,
+            ..Default::default()
+        };
+        assert_eq!(to_romaji_with_opt("いすゞ", options.clone()), "isuzu");
+        assert_eq!(to_romaji_with_opt("バナヽ", options.clone()), "banana");
+        assert_eq!(to_romaji_with_opt("時々こゝろ", options), "時時kokoro");
+    }
+    #[test]
+    fn reads_historical_kana() {
+        let options = Options {
+            historical_kana: true,
+            ..Default::default()
+        };
+        assert_eq!(to_romaji_with_opt("てふてふ", options.clone()), "chouchou");
+        assert_eq!(
+            to_romaji_with_opt("けふは山へゆかう", options.clone()),
+            "kyouha山heyukou"
+        );
+        assert_eq!(to_romaji_with_opt("ヰスキー", options.clone()), "isukii");
+        let report = to_romaji_with_report("けふは山", options);
+        assert_eq!(
+            (report.passthrough[0].start, report.passthrough[0].end),
+            (3, 4)
+        );
+    }
+    #[test]
+    fn romanizes_extended_katakana() {
+        let options = Options {
+            extended_katakana: true,
+            ..Default::default()
+        };
+        assert_eq!(to_romaji("イタㇰ"), "itaㇰ");
+        assert_eq!(
+            to_romaji_with_opt("アイヌ イタㇰ", options.clone()),
+            "ainu itak"
+        );
+        assert_eq!(to_romaji_with_opt("カㇺイ", options.clone()), "kami");
+        assert_eq!(to_romaji_with_opt("アㇷ゚", options.clone()), "ap");
+        assert_eq!(to_romaji_with_opt("ト゚ノ", options.clone()), "tuno");
+        assert_eq!(to_romaji_with_opt("クヮーシ", options.clone()), "kwaashi");
+        assert_eq!(to_romaji_with_opt("ッワー", options.clone()), "'waa");
+        assert_eq!(to_romaji_with_opt("ウォー", options.clone()), "woo");
+        assert_eq!(to_romaji_with_opt("ティーダ", options), "tiida");
+    }
+    #[test]
+    fn supplementary_kana() {
+        assert_eq!(to_romaji("\u{1B132}\u{1B152}\u{1B001}"), "kowoye");
+        assert_eq!(to_romaji("\u{1B155}\u{1B164}\u{1B167}"), "kowin");
+        assert_eq!(to_romaji("カ\u{1AFF0}\u{1AFF7}"), "ka\u{1AFF0}\u{1AFF7}");
+    }
 
     #[test]
     fn convert_katakana_to_romaji() {
@@ -228,6 +777,751 @@ mod tests {
         }
     }
 
+    mod kunrei {
+        use super::*;
+
+        fn with_kunrei() -> Options {
+            Options {
+                romanization: Romanization::Kunrei,
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn hepburn_is_default() {
+            assert_eq!(to_romaji("しつじゃ"), "shitsuja");
+        }
+        #[test]
+        fn converts_to_kunrei() {
+            assert_eq!(to_romaji_with_opt("しつじゃ", with_kunrei()), "situzya");
+            assert_eq!(to_romaji_with_opt("ふじさん", with_kunrei()), "huzisan");
+            assert_eq!(to_romaji_with_opt("ちぢむ", with_kunrei()), "tizimu");
+            assert_eq!(to_romaji_with_opt("ほんを", with_kunrei()), "hon'o");
+        }
+        #[test]
+        fn yoon() {
+            assert_eq!(
+                to_romaji_with_opt("しゃしゅしょ ちゃちゅちょ じゃじゅじょ", with_kunrei()),
+                "syasyusyo tyatyutyo zyazyuzyo"
+            );
+        }
+        #[test]
+        fn sokuon() {
+            assert_eq!(to_romaji_with_opt("ちょっと", with_kunrei()), "tyotto");
+            assert_eq!(to_romaji_with_opt("まっちゃ", with_kunrei()), "mattya");
+            assert_eq!(to_romaji_with_opt("いっしょ", with_kunrei()), "issyo");
+            assert_eq!(to_romaji_with_opt("みっつ", with_kunrei()), "mittu");
+        }
+        #[test]
+        fn long_vowels() {
+            assert_eq!(to_romaji_with_opt("シーツ", with_kunrei()), "siitu");
+            assert_eq!(to_romaji_with_opt("とうきょう", with_kunrei()), "toukyou");
+        }
+        #[test]
+        fn upcase_katakana() {
+            assert_eq!(
+                to_romaji_with_opt(
+                    "ツナ",
+                    Options {
+                        upcase_katakana: true,
+                        ..with_kunrei()
+                    }
+                ),
+                "TUNA"
+            );
+        }
+    }
+
+    mod nihon {
+        use super::*;
+        use crate::to_kana::to_kana_with_opt;
+
+        fn with_nihon() -> Options {
+            Options {
+                romanization: Romanization::Nihon,
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn converts_to_nihon() {
+            assert_eq!(to_romaji_with_opt("しつじゃ", with_nihon()), "situzya");
+            assert_eq!(to_romaji_with_opt("ぢづを", with_nihon()), "diduwo");
+            assert_eq!(
+                to_romaji_with_opt("ぢゃぢゅぢょ", with_nihon()),
+                "dyadyudyo"
+            );
+            assert_eq!(to_romaji_with_opt("ゐゑ", with_nihon()), "wiwe");
+            assert_eq!(to_romaji_with_opt("くゎぐゎ", with_nihon()), "kwagwa");
+            assert_eq!(to_romaji_with_opt("はなぢ", with_nihon()), "hanadi");
+            assert_eq!(to_romaji_with_opt("つづく", with_nihon()), "tuduku");
+            assert_eq!(to_romaji_with_opt("ほんを", with_nihon()), "honwo");
+        }
+        #[test]
+        fn small_kana() {
+            assert_eq!(to_romaji_with_opt("ぁゃゎ", with_nihon()), "xaxyaxwa");
+            assert_eq!(to_romaji_with_opt("ふぁ つぁ", with_nihon()), "huxa tuxa");
+            assert_eq!(to_romaji_with_opt("てぃ くぃ", with_nihon()), "texi kuxi");
+            assert_eq!(to_romaji_with_opt("あっ", with_nihon()), "axtu");
+        }
+        #[test]
+        fn sokuon() {
+            assert_eq!(to_romaji_with_opt("まっちゃ", with_nihon()), "mattya");
+            assert_eq!(to_romaji_with_opt("っづ", with_nihon()), "ddu");
+        }
+        #[test]
+        fn round_trips_through_to_kana() {
+            let kana = "あいうえお かきくけこ がぎぐげご さしすせそ ざじずぜぞ たちつてと \
+                        だぢづでど なにぬねの はひふへほ ばびぶべぼ ぱぴぷぺぽ まみむめも やゆよ \
+                        らりるれろ わゐゑを ん きゃきゅきょ ぎゃぎゅぎょ しゃしゅしょ \
+                        じゃじゅじょ ちゃちゅちょ ぢゃぢゅぢょ にゃにゅにょ ひゃひゅひょ \
+                        びゃびゅびょ ぴゃぴゅぴょ みゃみゅみょ りゃりゅりょ くゎぐゎ かっぱ \
+                        まっちゃ はなぢ つづく ほんを きんえん しんよう";
+            let romaji = to_romaji_with_opt(kana, with_nihon());
+            assert_eq!(to_kana_with_opt(&romaji, with_nihon()), kana);
+            let kana = "ぁぃぅぇぉ ゃゅょ ゎ っ あっ んっ ふぁ つぁ てぃ でゅ うぃ ゔぁ くぃ \
+                        くゎぁ ちぇ いぇ しぇ じぇ とぅ どぅ ぐゎ っふぁ きゃっ";
+            let romaji = to_romaji_with_opt(kana, with_nihon());
+            assert_eq!(to_kana_with_opt(&romaji, with_nihon()), kana);
+            let katakana = "ヂヅヲヰヱ クヮグヮ ヂャヂュヂョ";
+            let romaji = to_romaji_with_opt(
+                katakana,
+                Options {
+                    upcase_katakana: true,
+                    ..with_nihon()
+                },
+            );
+            assert_eq!(romaji, "DIDUWOWIWE KWAGWA DYADYUDYO");
+            assert_eq!(to_kana_with_opt(&romaji, with_nihon()), katakana);
+            let katakana = "ファイト ティッシュ ヴァ ァ ッ";
+            let romaji = to_romaji_with_opt(
+                katakana,
+                Options {
+                    upcase_katakana: true,
+                    ..with_nihon()
+                },
+            );
+            assert_eq!(to_kana_with_opt(&romaji, with_nihon()), katakana);
+        }
+    }
+
+    mod long_vowels {
+        use super::*;
+
+        fn with_long_vowels(long_vowels: LongVowels) -> Options {
+            Options {
+                long_vowels,
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn unchanged_by_default() {
+            assert_eq!(to_romaji("とうきょう"), "toukyou");
+            assert_eq!(to_romaji("スーパー"), "suupaa");
+        }
+        #[test]
+        fn macron() {
+            let opt = with_long_vowels(LongVowels::Macron);
+            assert_eq!(to_romaji_with_opt("とうきょう", opt.clone()), "tōkyō");
+            assert_eq!(to_romaji_with_opt("おおさか", opt.clone()), "ōsaka");
+            assert_eq!(to_romaji_with_opt("スーパー", opt.clone()), "sūpā");
+            assert_eq!(to_romaji_with_opt("缶コーヒー", opt.clone()), "缶kōhī");
+            assert_eq!(to_romaji_with_opt("ビール", opt.clone()), "bīru");
+            assert_eq!(to_romaji_with_opt("おかあさん", opt.clone()), "okāsan");
+            assert_eq!(to_romaji_with_opt("くうき", opt.clone()), "kūki");
+            assert_eq!(to_romaji_with_opt("おねえさん", opt.clone()), "onēsan");
+        }
+        #[test]
+        fn ii_and_ei_are_not_lengthened() {
+            let opt = with_long_vowels(LongVowels::Macron);
+            assert_eq!(to_romaji_with_opt("おにいさん", opt.clone()), "oniisan");
+            assert_eq!(to_romaji_with_opt("せんせい", opt), "sensei");
+        }
+        #[test]
+        fn only_one_vowel_is_merged() {
+            assert_eq!(
+                to_romaji_with_opt("おおう", with_long_vowels(LongVowels::Macron)),
+                "ōu"
+            );
+        }
+        #[test]
+        fn circumflex() {
+            let opt = with_long_vowels(LongVowels::Circumflex);
+            assert_eq!(to_romaji_with_opt("とうきょう", opt.clone()), "tôkyô");
+            assert_eq!(to_romaji_with_opt("スーパー", opt), "sûpâ");
+        }
+        #[test]
+        fn doubled() {
+            let opt = with_long_vowels(LongVowels::Doubled);
+            assert_eq!(to_romaji_with_opt("とうきょう", opt.clone()), "tookyoo");
+            assert_eq!(to_romaji_with_opt("おおさか", opt.clone()), "oosaka");
+            assert_eq!(to_romaji_with_opt("スーパー", opt), "suupaa");
+        }
+        #[test]
+        fn ou_and_oo_are_only_kept_apart_unchanged() {
+            assert_eq!(to_romaji("とうり とおり"), "touri toori");
+            assert_eq!(
+                to_romaji_with_opt("とうり とおり", with_long_vowels(LongVowels::Doubled)),
+                "toori toori"
+            );
+            assert_eq!(
+                to_romaji_with_opt("とうり とおり", with_long_vowels(LongVowels::Macron)),
+                "tōri tōri"
+            );
+        }
+        #[test]
+        fn hiragana_long_dash_stays_a_hyphen() {
+            assert_eq!(
+                to_romaji_with_opt("ばつげーむ", with_long_vowels(LongVowels::Macron)),
+                "batsuge-mu"
+            );
+        }
+        #[test]
+        fn upcase_katakana() {
+            assert_eq!(
+                to_romaji_with_opt(
+                    "トーキョー と おおさか",
+                    Options {
+                        upcase_katakana: true,
+                        ..with_long_vowels(LongVowels::Macron)
+                    }
+                ),
+                "TŌKYŌ to ōsaka"
+            );
+        }
+        #[test]
+        fn kunrei() {
+            assert_eq!(
+                to_romaji_with_opt(
+                    "ちゅうしゃ",
+                    Options {
+                        romanization: Romanization::Kunrei,
+                        ..with_long_vowels(LongVowels::Circumflex)
+                    }
+                ),
+                "tyûsya"
+            );
+        }
+    }
+
+    mod passport {
+        use super::*;
+
+        fn with_passport() -> Options {
+            Options {
+                romanization: Romanization::Passport,
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn omits_long_vowels() {
+            assert_eq!(to_romaji_with_opt("とうきょう", with_passport()), "tokyo");
+            assert_eq!(to_romaji_with_opt("おおの", with_passport()), "ono");
+            assert_eq!(to_romaji_with_opt("ゆうこ", with_passport()), "yuko");
+        }
+        #[test]
+        fn oh_spelling() {
+            let opt = Options {
+                long_vowels: LongVowels::Oh,
+                ..with_passport()
+            };
+            assert_eq!(to_romaji_with_opt("さとう", opt.clone()), "satoh");
+            assert_eq!(to_romaji_with_opt("おおの", opt.clone()), "ohno");
+            assert_eq!(to_romaji_with_opt("ゆうこ", opt), "yuko");
+        }
+        #[test]
+        fn m_before_b_m_p() {
+            assert_eq!(to_romaji_with_opt("なんば", with_passport()), "namba");
+            assert_eq!(to_romaji_with_opt("ほんま", with_passport()), "homma");
+            assert_eq!(to_romaji_with_opt("さんぺい", with_passport()), "sampei");
+            assert_eq!(
+                to_romaji_with_opt("じゅんびょう", with_passport()),
+                "jumbyo"
+            );
+            assert_eq!(to_romaji_with_opt("けんじ", with_passport()), "kenji");
+            assert_eq!(to_romaji_with_opt("なんば", Options::default()), "nanba");
+        }
+        #[test]
+        fn no_apostrophe() {
+            assert_eq!(to_romaji_with_opt("しんいち", with_passport()), "shinichi");
+            assert_eq!(to_romaji_with_opt("じゅんや", with_passport()), "junya");
+        }
+        #[test]
+        fn sokuon() {
+            assert_eq!(to_romaji_with_opt("はっとり", with_passport()), "hattori");
+            assert_eq!(to_romaji_with_opt("えっちゅう", with_passport()), "etchu");
+        }
+    }
+
+    mod wapuro {
+        use super::*;
+        use crate::to_kana::to_kana;
+
+        fn with_wapuro() -> Options {
+            Options {
+                romanization: Romanization::Wapuro,
+                ..Default::default()
+            }
+        }
+
+        fn round_trip(kana: &str, expected: &str) {
+            let romaji = to_romaji_with_opt(kana, with_wapuro());
+            assert_eq!(romaji, expected);
+            assert_eq!(to_kana(&romaji), kana);
+        }
+
+        #[test]
+        fn katakana_is_upper_cased() {
+            round_trip("ひらがな カタカナ", "hiragana KATAKANA");
+        }
+        #[test]
+        fn syllabic_n() {
+            round_trip("んよ んあ ンヨ", "n'yo n'a N'YO");
+            round_trip("きンあ", "kiN'a");
+            round_trip("んっ", "nxtsu");
+        }
+        #[test]
+        fn sokuon() {
+            round_trip("ちゃっちゃ", "chaccha");
+            round_trip("っ", "xtsu");
+            round_trip("っあ", "xtsua");
+            round_trip("かっ", "kaxtsu");
+            round_trip("かッカ", "kaKKA");
+            round_trip("かっカ", "kaxtsuKA");
+        }
+        #[test]
+        fn small_kana() {
+            round_trip("ぁぃぅぇぉゃゅょゎ", "xaxixuxexoxyaxyuxyoxwa");
+            round_trip("くぃ", "kuxi");
+        }
+        #[test]
+        fn obsolete_and_rare_kana() {
+            round_trip("ゐゑを", "wyiwyewo");
+            round_trip("ぢづ", "didu");
+            round_trip("ヴァ", "VA");
+            round_trip("ヶ", "ヶ");
+            round_trip("ヸ", "ヸ");
+        }
+        #[test]
+        fn mixed_scripts() {
+            round_trip("キゃ", "KIxya");
+        }
+        #[test]
+        fn long_dash() {
+            round_trip("スーパー", "SU-PA-");
+            round_trip("げーむ", "ge-mu");
+        }
+    }
+
+    mod particles {
+        use super::*;
+
+        fn with_particles() -> Options {
+            Options {
+                particles: true,
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn off_by_default() {
+            assert_eq!(to_romaji("わたし は"), "watashi ha");
+        }
+        #[test]
+        fn space_separated() {
+            assert_eq!(
+                to_romaji_with_opt("わたし は がくせい です", with_particles()),
+                "watashi wa gakusei desu"
+            );
+            assert_eq!(
+                to_romaji_with_opt("がっこう　へ　いく", with_particles()),
+                "gakkou e iku"
+            );
+            assert_eq!(
+                to_romaji_with_opt("ほん を よむ。", with_particles()),
+                "hon o yomu."
+            );
+            assert_eq!(
+                to_romaji_with_opt("これ は、", with_particles()),
+                "kore wa,"
+            );
+        }
+        #[test]
+        fn only_standalone_kana() {
+            assert_eq!(
+                to_romaji_with_opt("はな へや をかし", with_particles()),
+                "hana heya wokashi"
+            );
+            assert_eq!(to_romaji_with_opt("ハ", with_particles()), "ha");
+        }
+        #[test]
+        fn marker() {
+            assert_eq!(
+                to_romaji_with_opt("わたし_はがくせい", with_particles()),
+                "watashiwagakusei"
+            );
+            assert_eq!(to_romaji_with_opt("a_b", with_particles()), "a_b");
+        }
+        #[test]
+        fn after_kanji() {
+            assert_eq!(to_romaji_with_opt("私は学生", with_particles()), "私wa学生");
+            assert_eq!(
+                to_romaji_with_opt("東京へ行く", with_particles()),
+                "東京e行ku"
+            );
+            assert_eq!(to_romaji_with_opt("本を", with_particles()), "本o");
+            assert_eq!(to_romaji_with_opt("本をよむ", with_particles()), "本oyomu");
+            assert_eq!(
+                to_romaji_with_opt("私はいます", with_particles()),
+                "私waimasu"
+            );
+            assert_eq!(to_romaji_with_opt("山へいく", with_particles()), "山eiku");
+        }
+        #[test]
+        fn after_katakana() {
+            assert_eq!(
+                to_romaji_with_opt("ペンはあかい", with_particles()),
+                "penwaakai"
+            );
+        }
+        #[test]
+        fn end_of_hiragana_word() {
+            assert_eq!(
+                to_romaji_with_opt("ぼくは がくせい", with_particles()),
+                "bokuwa gakusei"
+            );
+            assert_eq!(
+                to_romaji_with_opt("これは\nペン", with_particles()),
+                "korewa\npen"
+            );
+            assert_eq!(
+                to_romaji_with_opt("これはペンです", with_particles()),
+                "korewapendesu"
+            );
+            assert_eq!(
+                to_romaji_with_opt("わたしは学生", with_particles()),
+                "watashiwa学生"
+            );
+            assert_eq!(to_romaji_with_opt("はは", with_particles()), "haha");
+            assert_eq!(to_romaji_with_opt("はなへや", with_particles()), "hanaheya");
+        }
+        #[test]
+        fn kunrei() {
+            assert_eq!(
+                to_romaji_with_opt(
+                    "ほん を",
+                    Options {
+                        romanization: Romanization::Kunrei,
+                        ..with_particles()
+                    }
+                ),
+                "hon o"
+            );
+        }
+    }
+
+    mod syllabic_n {
+        use super::*;
+
+        fn with_syllabic_n(syllabic_n: SyllabicN) -> Options {
+            Options {
+                syllabic_n,
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn unchanged() {
+            assert_eq!(to_romaji("おんよみ"), "on'yomi");
+            assert_eq!(
+                to_romaji_with_opt(
+                    "しんいち",
+                    Options {
+                        romanization: Romanization::Passport,
+                        ..Default::default()
+                    }
+                ),
+                "shinichi"
+            );
+        }
+        #[test]
+        fn apostrophe() {
+            assert_eq!(
+                to_romaji_with_opt("おんよみ", with_syllabic_n(SyllabicN::Apostrophe)),
+                "on'yomi"
+            );
+            assert_eq!(
+                to_romaji_with_opt(
+                    "しんいち",
+                    Options {
+                        romanization: Romanization::Passport,
+                        ..with_syllabic_n(SyllabicN::Apostrophe)
+                    }
+                ),
+                "shin'ichi"
+            );
+        }
+        #[test]
+        fn hyphen() {
+            let options = with_syllabic_n(SyllabicN::Hyphen);
+            assert_eq!(to_romaji_with_opt("おんよみ", options.clone()), "on-yomi");
+            assert_eq!(to_romaji_with_opt("きんえん", options.clone()), "kin-en");
+            assert_eq!(to_romaji_with_opt("ホンア", options.clone()), "hon-a");
+            assert_eq!(to_romaji_with_opt("こんな", options), "konna");
+        }
+        #[test]
+        fn doubled() {
+            let options = with_syllabic_n(SyllabicN::Doubled);
+            assert_eq!(to_romaji_with_opt("おんよみ", options.clone()), "onnyomi");
+            assert_eq!(
+                to_romaji_with_opt(
+                    "キンエン",
+                    Options {
+                        upcase_katakana: true,
+                        ..options
+                    }
+                ),
+                "KINNEN"
+            );
+        }
+        #[test]
+        fn plain() {
+            let options = with_syllabic_n(SyllabicN::Plain);
+            assert_eq!(to_romaji_with_opt("おんよみ", options.clone()), "onyomi");
+            assert_eq!(
+                to_romaji_with_opt(
+                    "じゅんいちろう",
+                    Options {
+                        romanization: Romanization::Kunrei,
+                        ..options
+                    }
+                ),
+                "zyunitirou"
+            );
+        }
+        #[test]
+        fn with_separator() {
+            assert_eq!(
+                to_romaji_with_opt(
+                    "きんえん",
+                    Options {
+                        separator: Some(Separator::Mora("-".to_string())),
+                        ..with_syllabic_n(SyllabicN::Doubled)
+                    }
+                ),
+                "ki-n-e-n"
+            );
+        }
+    }
+
+    mod final_sokuon {
+        use super::*;
+
+        fn with_final_sokuon(final_sokuon: FinalSokuon) -> Options {
+            Options {
+                final_sokuon,
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn unchanged() {
+            assert_eq!(to_romaji("っ"), "");
+            assert_eq!(to_romaji("あっ"), "a");
+        }
+        #[test]
+        fn apostrophe() {
+            let options = with_final_sokuon(FinalSokuon::Apostrophe);
+            assert_eq!(to_romaji_with_opt("あっ", options.clone()), "a'");
+            assert_eq!(to_romaji_with_opt("アッ！", options.clone()), "a'!");
+            assert_eq!(to_romaji_with_opt("あっ、そう", options.clone()), "a',sou");
+            assert_eq!(to_romaji_with_opt("かっぱ", options), "kappa");
+        }
+        #[test]
+        fn small_tsu() {
+            let options = with_final_sokuon(FinalSokuon::SmallTsu);
+            assert_eq!(to_romaji_with_opt("っ", options.clone()), "xtsu");
+            assert_eq!(to_romaji_with_opt("あっ。", options), "axtsu.");
+        }
+        #[test]
+        fn omitted() {
+            assert_eq!(
+                to_romaji_with_opt(
+                    "あっ",
+                    Options {
+                        romanization: Romanization::Wapuro,
+                        ..with_final_sokuon(FinalSokuon::Omitted)
+                    }
+                ),
+                "a"
+            );
+        }
+    }
+
+    mod custom_romaji_mapping {
+        use super::*;
+
+        fn with_mapping(mapping: &[(&str, &str)]) -> Options {
+            Options {
+                custom_romaji_mapping: Some(mapping.iter().copied().collect()),
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn overrides_mappings() {
+            let options = with_mapping(&[("じ", "zi"), ("ふ", "hu")]);
+            assert_eq!(to_romaji_with_opt("ふじ", options.clone()), "huzi");
+            assert_eq!(to_romaji_with_opt("フジ", options.clone()), "huzi");
+            assert_eq!(
+                to_romaji_with_opt(
+                    "フジ",
+                    Options {
+                        upcase_katakana: true,
+                        ..options
+                    }
+                ),
+                "HUZI"
+            );
+        }
+        #[test]
+        fn keeps_the_longest_match() {
+            let options = with_mapping(&[("ん", "m"), ("じ", "zi")]);
+            assert_eq!(to_romaji_with_opt("さんぽ", options.clone()), "sampo");
+            assert_eq!(to_romaji_with_opt("ほんや", options.clone()), "hon'ya");
+            assert_eq!(to_romaji_with_opt("じゃじ", options), "jazi");
+        }
+        #[test]
+        fn adds_mappings() {
+            let options = with_mapping(&[("ゟ", "yori"), ("ゔゃ", "vya"), ("ゝゝゝ", "...")]);
+            assert_eq!(to_romaji_with_opt("ゟ", options.clone()), "yori");
+            assert_eq!(to_romaji_with_opt("ゔゃ", options.clone()), "vya");
+            assert_eq!(to_romaji_with_opt("ゔぁゝゝ", options.clone()), "vaゝゝ");
+            assert_eq!(to_romaji_with_opt("ゝゝゝ", options), "...");
+        }
+        #[test]
+        fn is_merged_into_the_selected_tree() {
+            let options = Options {
+                romanization: Romanization::Kunrei,
+                ..with_mapping(&[("ふ", "fu")])
+            };
+            assert_eq!(to_romaji_with_opt("ふじ", options), "fuzi");
+        }
+        #[test]
+        fn empty_and_non_latin_outputs() {
+            let options = Options {
+                separator: Some(Separator::Mora("-".to_string())),
+                syllabic_n: SyllabicN::Hyphen,
+                ..with_mapping(&[("ん", ""), ("か", "カ"), ("っ", "ッ")])
+            };
+            assert_eq!(to_romaji_with_opt("かんかっ", options), "カカッ");
+            assert_eq!(
+                to_romaji_with_opt("てすと", with_mapping(&[])),
+                to_romaji("てすと")
+            );
+        }
+    }
+
+    mod separator {
+        use super::*;
+
+        fn with_mora() -> Options {
+            Options {
+                separator: Some(Separator::Mora("-".to_string())),
+                ..Default::default()
+            }
+        }
+
+        fn with_syllable() -> Options {
+            Options {
+                separator: Some(Separator::Syllable("-".to_string())),
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn mora() {
+            assert_eq!(to_romaji_with_opt("とうきょう", with_mora()), "to-u-kyo-u");
+            assert_eq!(to_romaji_with_opt("かっぱ", with_mora()), "ka-p-pa");
+            assert_eq!(to_romaji_with_opt("まっちゃ", with_mora()), "ma-t-cha");
+            assert_eq!(to_romaji_with_opt("ほんだ", with_mora()), "ho-n-da");
+            assert_eq!(to_romaji_with_opt("きんえん", with_mora()), "ki-n-e-n");
+            assert_eq!(to_romaji_with_opt("スーパー", with_mora()), "su-u-pa-a");
+        }
+        #[test]
+        fn syllable() {
+            assert_eq!(
+                to_romaji_with_opt("とうきょう", with_syllable()),
+                "tou-kyou"
+            );
+            assert_eq!(to_romaji_with_opt("かっぱ", with_syllable()), "kap-pa");
+            assert_eq!(to_romaji_with_opt("ほんだ", with_syllable()), "hon-da");
+            assert_eq!(to_romaji_with_opt("きんえん", with_syllable()), "kin-en");
+            assert_eq!(to_romaji_with_opt("んあ", with_syllable()), "n'a");
+            assert_eq!(to_romaji_with_opt("っぱ", with_syllable()), "ppa");
+        }
+        #[test]
+        fn syllable_with_macrons() {
+            assert_eq!(
+                to_romaji_with_opt(
+                    "とうきょう",
+                    Options {
+                        long_vowels: LongVowels::Macron,
+                        ..with_syllable()
+                    }
+                ),
+                "tō-kyō"
+            );
+        }
+        #[test]
+        fn only_kana_are_separated() {
+            assert_eq!(
+                to_romaji_with_opt("ひらがな　カタカナ、漢字です", with_mora()),
+                "hi-ra-ga-na ka-ta-ka-na,漢字de-su"
+            );
+            assert_eq!(to_romaji_with_opt("げーむ", with_mora()), "ge-mu");
+        }
+        #[test]
+        fn separator_string() {
+            assert_eq!(
+                to_romaji_with_opt(
+                    "ワニカニ",
+                    Options {
+                        upcase_katakana: true,
+                        separator: Some(Separator::Mora("·".to_string())),
+                        ..Default::default()
+                    }
+                ),
+                "WA·NI·KA·NI"
+            );
+        }
+    }
+
+    #[test]
+    fn halfwidth_katakana() {
+        assert_eq!(to_romaji("ｶﾞｿﾘﾝ"), "gasorin");
+        assert_eq!(to_romaji("ｽｰﾊﾟｰ｡"), "suupaa.");
+        assert_eq!(
+            to_romaji_with_opt(
+                "ｷｯﾌﾟ",
+                Options {
+                    upcase_katakana: true,
+                    ..Default::default()
+                }
+            ),
+            "KIPPU"
+        );
+    }
+
+    #[test]
+    fn vu() {
+        assert_eq!(to_romaji("ゔ"), "vu");
+        assert_eq!(to_romaji("ヴァヴィヴヴェヴォ"), "vavivuvevo");
+        assert_eq!(to_romaji("ゔぁっゔぁ"), "vavva");
+    }
+
     #[test]
     fn check_panic_issue_13() {
         assert_eq!(to_romaji("ウーッー"), "uu");
@@ -1,3 +1,5 @@
+use crate::custom_mapping::{CustomRomajiMapping, MappingNode};
+
 #[derive(Debug, Clone)]
 pub(crate) struct Node {
     pub transitions: Option<Vec<(char, Node)>>,
@@ -19,6 +21,35 @@ impl Node {
         (curr_node.output, i)
     }
 
+    /// Like [`Node::get`], on the tree with the `custom` mappings merged in. Custom outputs take
+    /// precedence, the last mappings first.
+    pub(crate) fn get_with_custom<'a, const N: usize>(
+        &'a self,
+        mut custom: [Option<&'a MappingNode>; N],
+        chars: &[char],
+    ) -> (&'a str, usize) {
+        let mut result = (self.output, 0);
+        let mut curr_node = Some(self);
+        for (i, char) in chars.iter().enumerate() {
+            curr_node = curr_node.and_then(|node| node.find_transition_node(*char));
+            custom = custom.map(|node| node.and_then(|node| node.find_transition_node(*char)));
+            if curr_node.is_none() && custom.iter().all(Option::is_none) {
+                break;
+            }
+            // a node only found in the custom mappings without an output is just part of a
+            // longer mapping
+            if let Some(output) = custom
+                .iter()
+                .rev()
+                .find_map(|node| node.and_then(|node| node.output.as_deref()))
+                .or(curr_node.map(|node| node.output))
+            {
+                result = (output, i + 1);
+            }
+        }
+        result
+    }
+
     pub(crate) fn find_transition_node(&self, char: char) -> Option<&Node> {
         if let Some(t) = &self.transitions {
             t.binary_search_by_key(&char, |t| t.0)
@@ -29,6 +60,55 @@ impl Node {
         }
     }
 
+    fn find_transition_mut(&mut self, char: char) -> Option<&mut Node> {
+        self.transitions
+            .as_mut()
+            .and_then(|t| t.iter_mut().find(|t| t.0 == char))
+            .map(|t| &mut t.1)
+    }
+
+    /// Removes the node reached by `kana` including all its transitions.
+    fn remove(&mut self, kana: &str) {
+        let mut chars = kana.chars().collect::<Vec<_>>();
+        let Some(last) = chars.pop() else {
+            return;
+        };
+        let mut curr_node = self;
+        for char in chars {
+            let Some(node) = curr_node.find_transition_mut(char) else {
+                return;
+            };
+            curr_node = node;
+        }
+        if let Some(transitions) = &mut curr_node.transitions {
+            transitions.retain(|t| t.0 != last);
+        }
+    }
+
+    /// Sets the output of the node reached by `kana`, appending the final node if it doesn't exist
+    /// yet. The tree has to be sorted afterwards.
+    fn set_output(&mut self, kana: &str, output: &'static str) {
+        let mut curr_node = self;
+        for char in kana.chars() {
+            let transitions = curr_node.transitions.get_or_insert_with(Vec::new);
+            let index = match transitions.iter().position(|t| t.0 == char) {
+                Some(index) => index,
+                None => {
+                    transitions.push((
+                        char,
+                        Node {
+                            transitions: None,
+                            output: "",
+                        },
+                    ));
+                    transitions.len() - 1
+                }
+            };
+            curr_node = &mut transitions[index].1;
+        }
+        curr_node.output = output;
+    }
+
     fn sort(&mut self) {
         if let Some(transitions) = &mut self.transitions {
             transitions.sort_by_key(|el| el.0);
@@ -39,6 +119,12 @@ impl Node {
     }
 }
 
+/// Tree without any mappings, for schemes bringing all of their own
+pub(crate) static EMPTY_NODE_TREE: Node = Node {
+    transitions: None,
+    output: "",
+};
+
 lazy_static! {
     pub(crate) static ref TO_ROMAJI_NODE_TREE: Node = {
         let transitions = Some(vec![
@@ -1730,18 +1816,32 @@ lazy_static! {
                             'ゔ',
                             Node {
                                 transitions: Some(vec![
+                                    (
+                                        'ぁ',
+                                        Node {
+                                            transitions: None,
+                                            output: "vva",
+                                        },
+                                    ),
                                     (
                                         'ぃ',
                                         Node {
                                             transitions: None,
-                                            output: "vvyi",
+                                            output: "vvi",
                                         },
                                     ),
                                     (
                                         'ぇ',
                                         Node {
                                             transitions: None,
-                                            output: "vvye",
+                                            output: "vve",
+                                        },
+                                    ),
+                                    (
+                                        'ぉ',
+                                        Node {
+                                            transitions: None,
+                                            output: "vvo",
                                         },
                                     ),
                                     (
@@ -1769,34 +1869,6 @@ lazy_static! {
                                 output: "vvu",
                             },
                         ),
-                        (
-                            'ゔ',
-                            Node {
-                                transitions: None,
-                                output: "vva",
-                            },
-                        ),
-                        (
-                            'ゔ',
-                            Node {
-                                transitions: None,
-                                output: "vvi",
-                            },
-                        ),
-                        (
-                            'ゔ',
-                            Node {
-                                transitions: None,
-                                output: "vve",
-                            },
-                        ),
-                        (
-                            'ゔ',
-                            Node {
-                                transitions: None,
-                                output: "vvo",
-                            },
-                        ),
                         (
                             '・',
                             Node {
@@ -2527,18 +2599,32 @@ lazy_static! {
                 'ゔ',
                 Node {
                     transitions: Some(vec![
+                        (
+                            'ぁ',
+                            Node {
+                                transitions: None,
+                                output: "va",
+                            },
+                        ),
                         (
                             'ぃ',
                             Node {
                                 transitions: None,
-                                output: "vyi",
+                                output: "vi",
                             },
                         ),
                         (
                             'ぇ',
                             Node {
                                 transitions: None,
-                                output: "vye",
+                                output: "ve",
+                            },
+                        ),
+                        (
+                            'ぉ',
+                            Node {
+                                transitions: None,
+                                output: "vo",
                             },
                         ),
                         (
@@ -2566,34 +2652,6 @@ lazy_static! {
                     output: "vu",
                 },
             ),
-            (
-                'ゔ',
-                Node {
-                    transitions: None,
-                    output: "va",
-                },
-            ),
-            (
-                'ゔ',
-                Node {
-                    transitions: None,
-                    output: "vi",
-                },
-            ),
-            (
-                'ゔ',
-                Node {
-                    transitions: None,
-                    output: "ve",
-                },
-            ),
-            (
-                'ゔ',
-                Node {
-                    transitions: None,
-                    output: "vo",
-                },
-            ),
             (
                 '・',
                 Node {
@@ -2677,7 +2735,284 @@ lazy_static! {
             transitions,
             output: "",
         };
+        for (kana, romaji) in SUPPLEMENTARY_KANA {
+            node.set_output(kana, romaji);
+        }
         node.sort();
         node
     };
+    pub(crate) static ref TO_ROMAJI_NODE_TREE_KUNREI: Node = {
+        let mut tree = TO_ROMAJI_NODE_TREE.clone();
+        for (kana, romaji, sokuon) in KUNREI_SHIKI {
+            tree.set_output(kana, romaji);
+            tree.set_output(&format!("っ{}", kana), sokuon);
+        }
+        // を is read as o, so it needs the same disambiguation as the other vowels
+        tree.set_output("んを", "n'o");
+        tree.sort();
+        tree
+    };
+    pub(crate) static ref TO_ROMAJI_NODE_TREE_PASSPORT: Node = {
+        let mut tree = TO_ROMAJI_NODE_TREE.clone();
+        for (kana, romaji) in PASSPORT_HEPBURN {
+            tree.set_output(kana, romaji);
+        }
+        tree.sort();
+        tree
+    };
+    pub(crate) static ref TO_ROMAJI_NODE_TREE_WAPURO: Node = {
+        let mut tree = TO_ROMAJI_NODE_TREE.clone();
+        for kana in WAPURO_REMOVED {
+            tree.remove(kana);
+        }
+        for (kana, romaji) in WAPURO {
+            tree.set_output(kana, romaji);
+        }
+        // っ is only written as a doubled consonant, everywhere else it is typed on its own
+        let sokuon = tree.find_transition_mut('っ').unwrap();
+        if let Some(transitions) = &mut sokuon.transitions {
+            transitions.retain(|t| {
+                let mut romaji = t.1.output.chars();
+                let first = romaji.next();
+                first.is_some_and(|c| c.is_ascii_alphabetic()) && first == romaji.next()
+            });
+        }
+        tree.sort();
+        tree
+    };
+    pub(crate) static ref TO_ROMAJI_NODE_TREE_NIHON: Node = {
+        let mut tree = TO_ROMAJI_NODE_TREE.clone();
+        for (kana, romaji, sokuon) in KUNREI_SHIKI.iter().chain(NIHON_SHIKI.iter()) {
+            tree.set_output(kana, romaji);
+            tree.set_output(&format!("っ{}", kana), sokuon);
+        }
+        for kana in NIHON_SHIKI_REMOVED {
+            tree.remove(kana);
+        }
+        for (kana, romaji) in NIHON_SHIKI_SMALL_KANA {
+            tree.set_output(kana, romaji);
+        }
+        // っ is only written as a doubled consonant, everywhere else it is written on its own
+        let sokuon = tree.find_transition_mut('っ').unwrap();
+        if let Some(transitions) = &mut sokuon.transitions {
+            transitions.retain(|t| {
+                let mut romaji = t.1.output.chars();
+                let first = romaji.next();
+                first.is_some_and(|c| c.is_ascii_alphabetic()) && first == romaji.next()
+            });
+        }
+        tree.sort();
+        tree
+    };
+    /// Overlay for [`Options::extended_katakana`](crate::Options::extended_katakana)
+    pub(crate) static ref EXTENDED_KATAKANA_MAPPING: CustomRomajiMapping =
+        CustomRomajiMapping::new(EXTENDED_KATAKANA);
 }
+
+/// Kunrei-shiki (ISO 3602) spellings which differ from Hepburn, as `(kana, romaji, romaji after
+/// っ)`.
+const KUNREI_SHIKI: [(&str, &str, &str); 29] = [
+    ("し", "si", "ssi"),
+    ("しぃ", "syi", "ssyi"),
+    ("しぇ", "sye", "ssye"),
+    ("しゃ", "sya", "ssya"),
+    ("しゅ", "syu", "ssyu"),
+    ("しょ", "syo", "ssyo"),
+    ("じ", "zi", "zzi"),
+    ("じぃ", "zyi", "zzyi"),
+    ("じぇ", "zye", "zzye"),
+    ("じゃ", "zya", "zzya"),
+    ("じゅ", "zyu", "zzyu"),
+    ("じょ", "zyo", "zzyo"),
+    ("ち", "ti", "tti"),
+    ("ちぃ", "tyi", "ttyi"),
+    ("ちぇ", "tye", "ttye"),
+    ("ちゃ", "tya", "ttya"),
+    ("ちゅ", "tyu", "ttyu"),
+    ("ちょ", "tyo", "ttyo"),
+    ("ぢ", "zi", "zzi"),
+    ("ぢぃ", "zyi", "zzyi"),
+    ("ぢぇ", "zye", "zzye"),
+    ("ぢゃ", "zya", "zzya"),
+    ("ぢゅ", "zyu", "zzyu"),
+    ("ぢょ", "zyo", "zzyo"),
+    ("つ", "tu", "ttu"),
+    ("ふ", "hu", "hhu"),
+    ("ゐ", "i", "i"),
+    ("ゑ", "e", "e"),
+    ("を", "o", "o"),
+];
+
+/// Nihon-shiki spellings which differ from Kunrei-shiki, as `(kana, romaji, romaji after っ)`.
+const NIHON_SHIKI: [(&str, &str, &str); 12] = [
+    ("ぢ", "di", "ddi"),
+    ("ぢぃ", "dyi", "ddyi"),
+    ("ぢぇ", "dye", "ddye"),
+    ("ぢゃ", "dya", "ddya"),
+    ("ぢゅ", "dyu", "ddyu"),
+    ("ぢょ", "dyo", "ddyo"),
+    ("づ", "du", "ddu"),
+    ("ゐ", "wi", "wwi"),
+    ("ゑ", "we", "wwe"),
+    ("を", "wo", "wwo"),
+    ("くゎ", "kwa", "kkwa"),
+    ("ぐゎ", "gwa", "ggwa"),
+];
+
+/// Kana which the Nihon-shiki tree doesn't combine, because their spellings are read back as
+/// き with a small kana (くゃ → kya → きゃ). They are written separately instead (くぃ → kuxi).
+const NIHON_SHIKI_REMOVED: [&str; 10] = [
+    "くぃ",
+    "くぇ",
+    "くゃ",
+    "くゅ",
+    "くょ",
+    "っくぃ",
+    "っくぇ",
+    "っくゃ",
+    "っくゅ",
+    "っくょ",
+];
+
+/// Nihon-shiki has no spellings for small kana, they are written with x as to_kana() reads them,
+/// so that they aren't read back as full-size kana.
+const NIHON_SHIKI_SMALL_KANA: [(&str, &str); 10] = [
+    ("ぁ", "xa"),
+    ("ぃ", "xi"),
+    ("ぅ", "xu"),
+    ("ぇ", "xe"),
+    ("ぉ", "xo"),
+    ("ゃ", "xya"),
+    ("ゅ", "xyu"),
+    ("ょ", "xyo"),
+    ("ゎ", "xwa"),
+    ("っ", "xtu"),
+];
+
+/// Kana of the supplementary blocks. Katakana with a hiragana counterpart are looked up as
+/// hiragana. The Taiwanese tone marks are not kana letters and are passed through.
+const SUPPLEMENTARY_KANA: [(&str, &str); 9] = [
+    ("\u{1B000}", "e"),
+    ("\u{1B001}", "ye"),
+    ("\u{1B11F}", "wu"),
+    ("\u{1B120}", "yi"),
+    ("\u{1B132}", "ko"),
+    ("\u{1B150}", "wi"),
+    ("\u{1B151}", "we"),
+    ("\u{1B152}", "wo"),
+    ("\u{1B167}", "n"),
+];
+
+/// Passport Hepburn spellings which differ from Hepburn. ん is not disambiguated before vowels.
+const PASSPORT_HEPBURN: [(&str, &str); 14] = [
+    ("んあ", "na"),
+    ("んい", "ni"),
+    ("んう", "nu"),
+    ("んえ", "ne"),
+    ("んお", "no"),
+    ("んや", "nya"),
+    ("んゆ", "nyu"),
+    ("んよ", "nyo"),
+    ("ゐ", "i"),
+    ("っゐ", "i"),
+    ("ゑ", "e"),
+    ("っゑ", "e"),
+    ("を", "o"),
+    ("っを", "o"),
+];
+
+/// Kana which the wāpuro tree doesn't combine, because to_kana() can't read the Hepburn spelling
+/// back. They are written separately instead (くぃ → kuxi).
+const WAPURO_REMOVED: [&str; 13] = [
+    "ヷ",
+    "くぃ",
+    "くぇ",
+    "くゃ",
+    "くゅ",
+    "くょ",
+    "っくぃ",
+    "っくぇ",
+    "っくゃ",
+    "っくゅ",
+    "っくょ",
+    "っゐ",
+    "っゑ",
+];
+
+/// Wāpuro spellings which differ from Hepburn, so that to_kana() converts them back to the same
+/// kana.
+const WAPURO: [(&str, &str); 32] = [
+    ("ぁ", "xa"),
+    ("ぃ", "xi"),
+    ("ぅ", "xu"),
+    ("ぇ", "xe"),
+    ("ぉ", "xo"),
+    ("ゃ", "xya"),
+    ("ゅ", "xyu"),
+    ("ょ", "xyo"),
+    ("ゎ", "xwa"),
+    ("っ", "xtsu"),
+    ("ぢ", "di"),
+    ("ぢぃ", "dyi"),
+    ("ぢぇ", "dye"),
+    ("ぢゃ", "dya"),
+    ("ぢゅ", "dyu"),
+    ("ぢょ", "dyo"),
+    ("づ", "du"),
+    ("ゐ", "wyi"),
+    ("ゑ", "wye"),
+    ("っち", "cchi"),
+    ("っちぃ", "cchyi"),
+    ("っちぇ", "cche"),
+    ("っちゃ", "ccha"),
+    ("っちゅ", "cchu"),
+    ("っちょ", "ccho"),
+    ("っぢ", "ddi"),
+    ("っぢぃ", "ddyi"),
+    ("っぢぇ", "ddye"),
+    ("っぢゃ", "ddya"),
+    ("っぢゅ", "ddyu"),
+    ("っぢょ", "ddyo"),
+    ("っづ", "ddu"),
+];
+
+/// Spellings of Ainu and the Ryukyuan languages. The small katakana of Ainu stand for a final
+/// consonant, ッ in Okinawan for a glottal stop.
+const EXTENDED_KATAKANA: [(&str, &str); 36] = [
+    ("ㇰ", "k"),
+    ("ㇱ", "s"),
+    ("ㇲ", "s"),
+    ("ㇳ", "t"),
+    ("ㇴ", "n"),
+    ("ㇵ", "h"),
+    ("ㇶ", "h"),
+    ("ㇷ", "h"),
+    ("ㇸ", "h"),
+    ("ㇹ", "h"),
+    ("ㇷ゚", "p"),
+    ("ㇺ", "m"),
+    ("ㇻ", "r"),
+    ("ㇼ", "r"),
+    ("ㇽ", "r"),
+    ("ㇾ", "r"),
+    ("ㇿ", "r"),
+    ("ト゚", "tu"),
+    ("ツ゚", "tu"),
+    ("セ゚", "ce"),
+    ("ウォ", "wo"),
+    ("クヮ", "kwa"),
+    ("グヮ", "gwa"),
+    ("クィ", "kwi"),
+    ("グィ", "gwi"),
+    ("ティ", "ti"),
+    ("ディ", "di"),
+    ("トゥ", "tu"),
+    ("ドゥ", "du"),
+    ("ッヮ", "'wa"),
+    ("ッワ", "'wa"),
+    ("ッヤ", "'ya"),
+    ("ッユ", "'yu"),
+    ("ッヨ", "'yo"),
+    ("ッウ", "'u"),
+    ("ッイ", "'i"),
+];
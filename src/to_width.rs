@@ -0,0 +1,123 @@
+use crate::constants::ZENKAKU_ASCII_OFFSET;
+use crate::options::WidthOptions;
+
+const FULLWIDTH_SPACE: char = '\u{3000}';
+
+/// Returns true if the class of the ASCII char is enabled in `options`.
+fn is_enabled(char: char, options: &WidthOptions) -> bool {
+    match char {
+        ' ' => options.space,
+        _ if char.is_ascii_alphabetic() => options.letters,
+        _ if char.is_ascii_digit() => options.digits,
+        _ if char.is_ascii_graphic() => options.punctuation,
+        _ => false,
+    }
+}
+
+/// Convert fullwidth (zenkaku) letters, digits, punctuation and spaces to ASCII
+pub fn to_halfwidth(input: &str) -> String {
+    to_halfwidth_with_opt(input, WidthOptions::default())
+}
+
+/// Convert fullwidth (zenkaku) letters, digits, punctuation and spaces to ASCII
+pub fn to_halfwidth_with_opt(input: &str, options: WidthOptions) -> String {
+    input
+        .chars()
+        .map(|char| {
+            let halfwidth = match char {
+                FULLWIDTH_SPACE => ' ',
+                '！'..='～' => char::from_u32(char as u32 - ZENKAKU_ASCII_OFFSET).unwrap_or(char),
+                _ => return char,
+            };
+            if is_enabled(halfwidth, &options) {
+                halfwidth
+            } else {
+                char
+            }
+        })
+        .collect()
+}
+
+/// Convert ASCII letters, digits, punctuation and spaces to fullwidth (zenkaku)
+pub fn to_fullwidth(input: &str) -> String {
+    to_fullwidth_with_opt(input, WidthOptions::default())
+}
+
+/// Convert ASCII letters, digits, punctuation and spaces to fullwidth (zenkaku)
+pub fn to_fullwidth_with_opt(input: &str, options: WidthOptions) -> String {
+    input
+        .chars()
+        .map(|char| {
+            if !is_enabled(char, &options) {
+                char
+            } else if char == ' ' {
+                FULLWIDTH_SPACE
+            } else {
+                char::from_u32(char as u32 + ZENKAKU_ASCII_OFFSET).unwrap_or(char)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sane_defaults() {
+        assert_eq!(to_halfwidth(""), "");
+        assert_eq!(to_fullwidth(""), "");
+    }
+
+    #[test]
+    fn converts_to_halfwidth() {
+        assert_eq!(to_halfwidth("ＡＢＣ１２３！"), "ABC123!");
+        assert_eq!(
+            to_halfwidth("ｗａｎａｋａｎａ　～＜＞＼＿"),
+            "wanakana ~<>\\_"
+        );
+        assert_eq!(
+            to_halfwidth("ひらがな、カタカナ。漢字"),
+            "ひらがな、カタカナ。漢字"
+        );
+    }
+
+    #[test]
+    fn converts_to_fullwidth() {
+        assert_eq!(to_fullwidth("ABC123!"), "ＡＢＣ１２３！");
+        assert_eq!(
+            to_fullwidth("wanakana ~<>\\_"),
+            "ｗａｎａｋａｎａ　～＜＞＼＿"
+        );
+        assert_eq!(to_fullwidth("ひらがな\n"), "ひらがな\n");
+    }
+
+    #[test]
+    fn converts_only_enabled_classes() {
+        let only_digits = WidthOptions {
+            letters: false,
+            punctuation: false,
+            space: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            to_halfwidth_with_opt("ＡＢＣ　１２３！", only_digits.clone()),
+            "ＡＢＣ　123！"
+        );
+        assert_eq!(
+            to_fullwidth_with_opt("ABC 123!", only_digits),
+            "ABC １２３!"
+        );
+        let without_space = WidthOptions {
+            space: false,
+            ..Default::default()
+        };
+        assert_eq!(to_fullwidth_with_opt("A B", without_space), "Ａ Ｂ");
+    }
+
+    #[test]
+    fn round_trips_printable_ascii() {
+        let ascii = (' '..='~').collect::<String>();
+        assert_eq!(to_halfwidth(&to_fullwidth(&ascii)), ascii);
+    }
+}
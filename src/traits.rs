@@ -22,7 +22,7 @@
 #[cfg(feature = "enable_regex")]
 use regex::Regex;
 
-use crate::Options;
+use crate::{ImeCandidates, Options, RomajiReport, UnconvertedRomaji, WidthOptions};
 
 /// The `wana_kana::ConvertJapanese` trait is implemented for `&str`, which allows
 /// conversion between kana and romaji.
@@ -70,6 +70,26 @@ pub trait ConvertJapanese {
     /// ```
     fn to_kana_with_opt(self, options: Options) -> String;
 
+    /// Convert Romaji to Kana, failing with the spans of romaji letters that can't be converted
+    ///
+    /// ```
+    /// use wana_kana::{ConvertJapanese, UnconvertedReason};
+    /// assert_eq!("onaji".to_kana_strict().unwrap(), "おなじ");
+    /// assert_eq!("onaji 漢字 123".to_kana_strict().unwrap(), "おなじ 漢字 123");
+    /// let err = "onajy".to_kana_strict().unwrap_err();
+    /// assert_eq!(err.spans[0].text, "jy");
+    /// assert_eq!(err.spans[0].reason, UnconvertedReason::DanglingConsonant);
+    /// ```
+    fn to_kana_strict(self) -> Result<String, UnconvertedRomaji>;
+
+    /// Convert Romaji to Kana, failing with the spans of romaji letters that can't be converted
+    ///
+    /// ```
+    /// use wana_kana::{ConvertJapanese, Options};
+    /// assert_eq!("we".to_kana_strict_with_opt(Options {use_obsolete_kana: true, ..Default::default() } ).unwrap(), "ゑ");
+    /// ```
+    fn to_kana_strict_with_opt(self, options: Options) -> Result<String, UnconvertedRomaji>;
+
     /// Convert input to [Hiragana](https://en.wikipedia.org/wiki/Hiragana)
     /// # Examples
     /// ```
@@ -107,11 +127,83 @@ pub trait ConvertJapanese {
     /// # Examples
     /// ```
     /// use wana_kana::ConvertJapanese;
-    /// use wana_kana::Options;
+    /// use wana_kana::{LongVowels, Options};
     /// assert_eq!("ひらがな　カタカナ".to_romaji_with_opt(Options {upcase_katakana: true, ..Default::default() } ), "hiragana KATAKANA");
+    /// assert_eq!("とうきょう".to_romaji_with_opt(Options {long_vowels: LongVowels::Macron, ..Default::default() } ), "tōkyō");
     /// ```
-
     fn to_romaji_with_opt(self, options: Options) -> String;
+
+    /// Convert kana to romaji, reporting the spans that were passed through without being
+    /// romanized, e.g. kanji
+    ///
+    /// ```
+    /// use wana_kana::{ConvertJapanese, Options, PassthroughCategory};
+    /// let report = "一抹げーむ ヶ月".to_romaji_with_report(Options::default());
+    /// assert_eq!(report.output, "一抹ge-mu ヶ月");
+    /// assert_eq!(report.passthrough[0].text, "一抹");
+    /// assert_eq!(report.passthrough[0].category, PassthroughCategory::Kanji);
+    /// assert_eq!((report.passthrough[1].start, report.passthrough[1].end), (6, 7));
+    /// assert_eq!(report.passthrough[1].category, PassthroughCategory::Kana);
+    /// ```
+    fn to_romaji_with_report(self, options: Options) -> RomajiReport;
+
+    /// Convert fullwidth (zenkaku) letters, digits, punctuation and spaces to ASCII
+    /// # Examples
+    /// ```
+    /// use wana_kana::ConvertJapanese;
+    /// assert_eq!("ＡＢＣ１２３！".to_halfwidth(), "ABC123!");
+    /// assert_eq!("ｗａｎａｋａｎａ　ワナカナ".to_halfwidth(), "wanakana ワナカナ");
+    /// ```
+    fn to_halfwidth(self) -> String;
+
+    /// Convert fullwidth (zenkaku) chars to ASCII, limited to the classes enabled in `options`.
+    /// # Examples
+    /// ```
+    /// use wana_kana::ConvertJapanese;
+    /// use wana_kana::WidthOptions;
+    /// assert_eq!("ＡＢＣ１２３！".to_halfwidth_with_opt(WidthOptions {letters: false, ..Default::default() } ), "ＡＢＣ123!");
+    /// ```
+    fn to_halfwidth_with_opt(self, options: WidthOptions) -> String;
+
+    /// Convert ASCII letters, digits, punctuation and spaces to fullwidth (zenkaku)
+    /// # Examples
+    /// ```
+    /// use wana_kana::ConvertJapanese;
+    /// assert_eq!("ABC123!".to_fullwidth(), "ＡＢＣ１２３！");
+    /// ```
+    fn to_fullwidth(self) -> String;
+
+    /// Convert ASCII chars to fullwidth (zenkaku), limited to the classes enabled in `options`.
+    /// # Examples
+    /// ```
+    /// use wana_kana::ConvertJapanese;
+    /// use wana_kana::WidthOptions;
+    /// assert_eq!("No. 1".to_fullwidth_with_opt(WidthOptions {digits: false, ..Default::default() } ), "Ｎｏ．　1");
+    /// ```
+    fn to_fullwidth_with_opt(self, options: WidthOptions) -> String;
+
+    /// Convert romaji being typed to the forms an IME offers: hiragana, katakana, halfwidth
+    /// katakana, fullwidth and halfwidth romaji
+    ///
+    /// ```
+    /// use wana_kana::ConvertJapanese;
+    /// let candidates = "kyou".to_ime_candidates();
+    /// assert_eq!(candidates.hiragana, "きょう");
+    /// assert_eq!(candidates.katakana, "キョウ");
+    /// assert_eq!(candidates.halfwidth_katakana, "ｷｮｳ");
+    /// assert_eq!(candidates.fullwidth_romaji, "ｋｙｏｕ");
+    /// assert_eq!(candidates.halfwidth_romaji, "kyou");
+    /// ```
+    fn to_ime_candidates(self) -> ImeCandidates;
+
+    /// Convert romaji being typed to the forms an IME offers, the options apply to the kana forms
+    ///
+    /// ```
+    /// use wana_kana::{ConvertJapanese, Options};
+    /// let candidates = "wi".to_ime_candidates_with_opt(Options {use_obsolete_kana: true, ..Default::default() } );
+    /// assert_eq!(candidates.katakana, "ヰ");
+    /// ```
+    fn to_ime_candidates_with_opt(self, options: Options) -> ImeCandidates;
 }
 
 impl ConvertJapanese for &str {
@@ -125,6 +217,16 @@ impl ConvertJapanese for &str {
         crate::to_kana::to_kana_with_opt(self, options)
     }
 
+    #[inline]
+    fn to_kana_strict(self) -> Result<String, UnconvertedRomaji> {
+        crate::to_kana::to_kana_strict(self)
+    }
+
+    #[inline]
+    fn to_kana_strict_with_opt(self, options: Options) -> Result<String, UnconvertedRomaji> {
+        crate::to_kana::to_kana_strict_with_opt(self, options)
+    }
+
     #[inline]
     fn to_hiragana(self) -> String {
         crate::to_hiragana::to_hiragana(self)
@@ -154,6 +256,41 @@ impl ConvertJapanese for &str {
     fn to_romaji_with_opt(self, options: Options) -> String {
         crate::to_romaji::to_romaji_with_opt(self, options)
     }
+
+    #[inline]
+    fn to_romaji_with_report(self, options: Options) -> RomajiReport {
+        crate::to_romaji::to_romaji_with_report(self, options)
+    }
+
+    #[inline]
+    fn to_halfwidth(self) -> String {
+        crate::to_width::to_halfwidth(self)
+    }
+
+    #[inline]
+    fn to_halfwidth_with_opt(self, options: WidthOptions) -> String {
+        crate::to_width::to_halfwidth_with_opt(self, options)
+    }
+
+    #[inline]
+    fn to_fullwidth(self) -> String {
+        crate::to_width::to_fullwidth(self)
+    }
+
+    #[inline]
+    fn to_fullwidth_with_opt(self, options: WidthOptions) -> String {
+        crate::to_width::to_fullwidth_with_opt(self, options)
+    }
+
+    #[inline]
+    fn to_ime_candidates(self) -> ImeCandidates {
+        crate::to_ime_candidates::to_ime_candidates(self)
+    }
+
+    #[inline]
+    fn to_ime_candidates_with_opt(self, options: Options) -> ImeCandidates {
+        crate::to_ime_candidates::to_ime_candidates_with_opt(self, options)
+    }
 }
 
 /// The `wana_kana::IsJapaneseStr` trait is implemented for `&str`, which allows easy
@@ -211,6 +348,18 @@ pub trait IsJapaneseStr {
     /// ```
     fn is_romaji(&self) -> bool;
 
+    /// Test if every char in `input` is [Romaji](https://en.wikipedia.org/wiki/Romaji) (allowing [Hepburn romanisation](https://en.wikipedia.org/wiki/Hepburn_romanization)),
+    /// also accepting Zenkaku latin letters, numbers and punctuation if `allow_zenkaku` is true
+    /// # Examples
+    /// ```
+    /// use wana_kana::IsJapaneseStr;
+    /// assert_eq!("ｈｅｌｌｏ".is_romaji_allow_zenkaku(true), true);
+    /// assert_eq!("ａ！b&c".is_romaji_allow_zenkaku(true), true);
+    /// assert_eq!("ｈｅｌｌｏ".is_romaji_allow_zenkaku(false), false);
+    /// assert_eq!("あアA".is_romaji_allow_zenkaku(true), false);
+    /// ```
+    fn is_romaji_allow_zenkaku(&self, allow_zenkaku: bool) -> bool;
+
     #[cfg_attr(docsrs, doc(cfg(feature = "enable_regex")))]
     #[cfg(feature = "enable_regex")]
     /// Test if every char in `input` is [Romaji](https://en.wikipedia.org/wiki/Romaji) (allowing [Hepburn romanisation](https://en.wikipedia.org/wiki/Hepburn_romanization)
@@ -349,6 +498,11 @@ impl IsJapaneseStr for &str {
         crate::is_romaji::is_romaji(self)
     }
 
+    #[inline]
+    fn is_romaji_allow_zenkaku(&self, allow_zenkaku: bool) -> bool {
+        crate::is_romaji::is_romaji_allow_zenkaku(self, allow_zenkaku)
+    }
+
     #[cfg(feature = "enable_regex")]
     #[inline]
     fn is_romaji_with_whitelist(&self, allowed: Option<&Regex>) -> bool {
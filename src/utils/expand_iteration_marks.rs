@@ -0,0 +1,160 @@
+use std::borrow::Cow;
+
+use crate::utils::is_char_kana::is_char_kana;
+use crate::utils::is_char_kanji::is_char_kanji;
+
+const HIRAGANA_ITERATION_MARK: char = 'ゝ';
+const HIRAGANA_VOICED_ITERATION_MARK: char = 'ゞ';
+const KATAKANA_ITERATION_MARK: char = 'ヽ';
+const KATAKANA_VOICED_ITERATION_MARK: char = 'ヾ';
+const KANJI_ITERATION_MARKS: [char; 2] = ['々', '〻'];
+
+/// Kana which are voiced by adding 1 to their code point
+const VOICEABLE: &str =
+    "かきくけこさしすせそたちつてとはひふへほカキクケコサシスセソタチツテトハヒフヘホ";
+/// Kana which are semi-voiced by adding 2 to their code point
+const SEMI_VOICEABLE: &str = "はひふへほハヒフヘホ";
+
+fn shift(kana: char, offset: i32) -> char {
+    char::from_u32((kana as i32 + offset) as u32).unwrap_or(kana)
+}
+
+/// Returns the kana without its (semi-)voicing mark.
+fn unvoiced(kana: char) -> char {
+    let is_shifted = |base: &str, offset: i32| base.contains(shift(kana, -offset));
+    match kana {
+        'ゔ' => 'う',
+        'ヴ' => 'ウ',
+        'ヷ'..='ヺ' => shift(kana, 'ワ' as i32 - 'ヷ' as i32),
+        _ if is_shifted(VOICEABLE, 1) => shift(kana, -1),
+        _ if is_shifted(SEMI_VOICEABLE, 2) => shift(kana, -2),
+        _ => kana,
+    }
+}
+
+/// Returns the voiced form of the kana, or the kana itself if it has none.
+fn voiced(kana: char) -> char {
+    let kana = unvoiced(kana);
+    match kana {
+        'う' => 'ゔ',
+        'ウ' => 'ヴ',
+        'ワ'..='ヲ' => shift(kana, 'ヷ' as i32 - 'ワ' as i32),
+        _ if VOICEABLE.contains(kana) => shift(kana, 1),
+        _ => kana,
+    }
+}
+
+fn is_iteration_mark(char: char) -> bool {
+    matches!(
+        char,
+        HIRAGANA_ITERATION_MARK
+            | HIRAGANA_VOICED_ITERATION_MARK
+            | KATAKANA_ITERATION_MARK
+            | KATAKANA_VOICED_ITERATION_MARK
+    ) || KANJI_ITERATION_MARKS.contains(&char)
+}
+
+/// Expand iteration marks into the chars they repeat
+///
+/// ゝ and ヽ repeat the preceding kana without voicing, ゞ and ヾ with voicing. 々 and 〻 repeat
+/// the preceding kanji, a run of them repeats as many kanji. Marks with nothing to repeat are
+/// passed through, like any other chars. Each mark is replaced by exactly one char.
+///
+/// # Examples
+///
+/// expand_iteration_marks('いすゞ')
+///
+/// // => "いすず"
+///
+/// expand_iteration_marks('部分々々')
+///
+/// // => "部分部分"
+///
+/// expand_iteration_marks('こゝろ')
+///
+/// // => "こころ"
+pub fn expand_iteration_marks(input: &str) -> Cow<'_, str> {
+    if !input.chars().any(is_iteration_mark) {
+        return Cow::Borrowed(input);
+    }
+    let chars = input.chars().collect::<Vec<_>>();
+    let mut output: Vec<char> = Vec::with_capacity(chars.len());
+    // Number of kanji iteration marks in the current run, and the position of the first one
+    let mut kanji_run = (0, 0);
+    for (pos, &char) in chars.iter().enumerate() {
+        let prev = output.last().copied();
+        let expanded = match char {
+            HIRAGANA_ITERATION_MARK | KATAKANA_ITERATION_MARK => {
+                prev.filter(|prev| is_char_kana(*prev)).map(unvoiced)
+            }
+            HIRAGANA_VOICED_ITERATION_MARK | KATAKANA_VOICED_ITERATION_MARK => {
+                prev.filter(|prev| is_char_kana(*prev)).map(voiced)
+            }
+            _ if KANJI_ITERATION_MARKS.contains(&char) => {
+                if pos == 0 || !KANJI_ITERATION_MARKS.contains(&chars[pos - 1]) {
+                    let len = chars[pos..]
+                        .iter()
+                        .take_while(|char| KANJI_ITERATION_MARKS.contains(char))
+                        .count();
+                    kanji_run = (len, pos);
+                }
+                let (len, start) = kanji_run;
+                // A run of marks repeats as many kanji, e.g. 部分々々, if there are that many
+                let repeats_run = len > 1
+                    && start >= len
+                    && output[start - len..start].iter().all(|c| is_char_kanji(*c));
+                if repeats_run {
+                    Some(output[pos - len])
+                } else {
+                    prev.filter(|prev| is_char_kanji(*prev))
+                }
+            }
+            _ => None,
+        };
+        output.push(expanded.unwrap_or(char));
+    }
+    Cow::Owned(output.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sane_defaults() {
+        assert_eq!(expand_iteration_marks(""), "");
+        assert!(matches!(
+            expand_iteration_marks("ひらがな"),
+            Cow::Borrowed(_)
+        ));
+    }
+    #[test]
+    fn repeats_kana() {
+        assert_eq!(expand_iteration_marks("こゝろ"), "こころ");
+        assert_eq!(expand_iteration_marks("いすゞ"), "いすず");
+        assert_eq!(expand_iteration_marks("ぶゝ"), "ぶふ");
+        assert_eq!(expand_iteration_marks("ばゞ"), "ばば");
+        assert_eq!(expand_iteration_marks("ぱゞ"), "ぱば");
+        assert_eq!(expand_iteration_marks("あゞ"), "ああ");
+        assert_eq!(expand_iteration_marks("うゞ"), "うゔ");
+    }
+    #[test]
+    fn repeats_katakana() {
+        assert_eq!(expand_iteration_marks("バナヽ"), "バナナ");
+        assert_eq!(expand_iteration_marks("ミスヾ"), "ミスズ");
+        assert_eq!(expand_iteration_marks("ワヾ"), "ワヷ");
+        assert_eq!(expand_iteration_marks("ヷヽ"), "ヷワ");
+    }
+    #[test]
+    fn repeats_kanji() {
+        assert_eq!(expand_iteration_marks("時々"), "時時");
+        assert_eq!(expand_iteration_marks("人〻"), "人人");
+        assert_eq!(expand_iteration_marks("部分々々"), "部分部分");
+        assert_eq!(expand_iteration_marks("の時々々"), "の時時時");
+    }
+    #[test]
+    fn passes_through_marks_without_a_char_to_repeat() {
+        assert_eq!(expand_iteration_marks("ゝ々"), "ゝ々");
+        assert_eq!(expand_iteration_marks("a々 時ゝ"), "a々 時ゝ");
+    }
+}
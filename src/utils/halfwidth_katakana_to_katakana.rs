@@ -0,0 +1,137 @@
+use std::borrow::Cow;
+
+use crate::constants::KANA_PUNCTUATION;
+
+/// Fullwidth forms of U+FF61 to U+FF9F
+pub(crate) const FULLWIDTH: [char; 63] = [
+    '。', '「', '」', '、', '・', 'ヲ', 'ァ', 'ィ', 'ゥ', 'ェ', 'ォ', 'ャ', 'ュ', 'ョ', 'ッ', 'ー',
+    'ア', 'イ', 'ウ', 'エ', 'オ', 'カ', 'キ', 'ク', 'ケ', 'コ', 'サ', 'シ', 'ス', 'セ', 'ソ', 'タ',
+    'チ', 'ツ', 'テ', 'ト', 'ナ', 'ニ', 'ヌ', 'ネ', 'ノ', 'ハ', 'ヒ', 'フ', 'ヘ', 'ホ', 'マ', 'ミ',
+    'ム', 'メ', 'モ', 'ヤ', 'ユ', 'ヨ', 'ラ', 'リ', 'ル', 'レ', 'ロ', 'ワ', 'ン', '゛', '゜',
+];
+
+pub(crate) const HALFWIDTH_VOICED_MARK: char = 'ﾞ';
+pub(crate) const HALFWIDTH_SEMI_VOICED_MARK: char = 'ﾟ';
+
+/// Returns the fullwidth form of a halfwidth katakana or punctuation char.
+fn to_fullwidth(char: char) -> Option<char> {
+    let index = (char as u32).checked_sub(KANA_PUNCTUATION[0])?;
+    FULLWIDTH.get(index as usize).copied()
+}
+
+/// Returns `kana` with the (semi-)voiced `mark` added, if there is such a kana.
+fn add_voicing_mark(kana: char, mark: char) -> Option<char> {
+    let shift = |offset: u32| char::from_u32(kana as u32 + offset);
+    match mark {
+        HALFWIDTH_VOICED_MARK if "カキクケコサシスセソタチツテトハヒフヘホ".contains(kana) => {
+            shift(1)
+        }
+        HALFWIDTH_SEMI_VOICED_MARK if "ハヒフヘホ".contains(kana) => shift(2),
+        HALFWIDTH_VOICED_MARK if kana == 'ウ' => Some('ヴ'),
+        HALFWIDTH_VOICED_MARK if ('ワ'..='ヲ').contains(&kana) => {
+            shift('ヷ' as u32 - 'ワ' as u32)
+        }
+        _ => None,
+    }
+}
+
+/// Convert halfwidth [Katakana](https://en.wikipedia.org/wiki/Half-width_kana) and punctuation
+/// to their fullwidth forms
+///
+/// The voicing marks ﾞ and ﾟ are merged into the preceding katakana, marks without a voiced form
+/// to merge with become ゛ and ゜. Passes through any other chars.
+///
+/// # Examples
+///
+/// halfwidth_katakana_to_katakana('ｶﾞｿﾘﾝ')
+///
+/// // => "ガソリン"
+///
+/// halfwidth_katakana_to_katakana('ｺﾝﾆﾁﾊ｡')
+///
+/// // => "コンニチハ。"
+pub fn halfwidth_katakana_to_katakana(input: &str) -> Cow<'_, str> {
+    if !input.chars().any(|char| to_fullwidth(char).is_some()) {
+        return Cow::Borrowed(input);
+    }
+    let mut output = String::with_capacity(input.len());
+    for char in input.chars() {
+        if char == HALFWIDTH_VOICED_MARK || char == HALFWIDTH_SEMI_VOICED_MARK {
+            if let Some(voiced) = output
+                .chars()
+                .last()
+                .and_then(|kana| add_voicing_mark(kana, char))
+            {
+                output.pop();
+                output.push(voiced);
+                continue;
+            }
+        }
+        output.push(to_fullwidth(char).unwrap_or(char));
+    }
+    Cow::Owned(output)
+}
+
+/// Returns the offset in `input` of each char of [`halfwidth_katakana_to_katakana`]`(input)`, in
+/// chars.
+pub(crate) fn halfwidth_katakana_to_katakana_offsets(input: &str) -> Vec<usize> {
+    let mut offsets = Vec::new();
+    let mut last = None;
+    for (offset, char) in input.chars().enumerate() {
+        if let Some(voiced) = last.and_then(|kana| add_voicing_mark(kana, char)) {
+            last = Some(voiced);
+            continue;
+        }
+        offsets.push(offset);
+        last = Some(to_fullwidth(char).unwrap_or(char));
+    }
+    offsets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_halfwidth_katakana() {
+        assert_eq!(
+            halfwidth_katakana_to_katakana("ｱｲｳｴｵ ｧｨｩｪｫ ｬｭｮｯｰ ｦﾝ"),
+            "アイウエオ ァィゥェォ ャュョッー ヲン"
+        );
+        assert_eq!(
+            halfwidth_katakana_to_katakana("｢ｶﾀｶﾅ｣､ﾃﾞｽ｡"),
+            "「カタカナ」、デス。"
+        );
+    }
+    #[test]
+    fn merges_voicing_marks() {
+        assert_eq!(halfwidth_katakana_to_katakana("ｶﾞｿﾘﾝ"), "ガソリン");
+        assert_eq!(halfwidth_katakana_to_katakana("ﾊﾞﾋﾟﾌﾞﾍﾟﾎﾞ"), "バピブペボ");
+        assert_eq!(halfwidth_katakana_to_katakana("ｳﾞｧﾜﾞｦﾞ"), "ヴァヷヺ");
+        assert_eq!(halfwidth_katakana_to_katakana("カﾞ"), "ガ");
+    }
+    #[test]
+    fn keeps_marks_without_a_voiced_form() {
+        assert_eq!(halfwidth_katakana_to_katakana("ｱﾞﾟｶﾟ"), "ア゛゜カ゜");
+        assert_eq!(halfwidth_katakana_to_katakana("ｶﾞﾞ"), "ガ゛");
+    }
+    #[test]
+    fn offsets() {
+        assert_eq!(
+            halfwidth_katakana_to_katakana_offsets("ｶﾞｿﾘﾝ ka"),
+            [0, 2, 3, 4, 5, 6, 7]
+        );
+        assert_eq!(halfwidth_katakana_to_katakana_offsets("ｶﾞﾞ"), [0, 2]);
+    }
+    #[test]
+    fn passes_other_chars_through() {
+        assert_eq!(
+            halfwidth_katakana_to_katakana("カタカナ abc"),
+            "カタカナ abc"
+        );
+        assert!(matches!(
+            halfwidth_katakana_to_katakana("カタカナ"),
+            Cow::Borrowed(_)
+        ));
+    }
+}
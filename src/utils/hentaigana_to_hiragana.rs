@@ -0,0 +1,84 @@
+use std::borrow::Cow;
+
+use crate::constants::HENTAIGANA;
+use crate::utils::is_char_hentaigana::is_char_hentaigana;
+
+/// Hiragana of the hentaigana U+1B002 to U+1B11E
+const HIRAGANA: [char; 285] = [
+    'あ', 'あ', 'あ', 'あ', 'い', 'い', 'い', 'い', 'う', 'う', 'う', 'う', 'う', 'え', 'え', 'え',
+    'え', 'え', 'お', 'お', 'お', 'か', 'か', 'か', 'か', 'か', 'か', 'か', 'か', 'か', 'か', 'か',
+    'か', 'き', 'き', 'き', 'き', 'き', 'き', 'き', 'き', 'く', 'く', 'く', 'く', 'く', 'く', 'く',
+    'け', 'け', 'け', 'け', 'け', 'け', 'こ', 'こ', 'こ', 'こ', 'さ', 'さ', 'さ', 'さ', 'さ', 'さ',
+    'さ', 'さ', 'し', 'し', 'し', 'し', 'し', 'し', 'す', 'す', 'す', 'す', 'す', 'す', 'す', 'す',
+    'せ', 'せ', 'せ', 'せ', 'せ', 'そ', 'そ', 'そ', 'そ', 'そ', 'そ', 'そ', 'た', 'た', 'た', 'た',
+    'ち', 'ち', 'ち', 'ち', 'ち', 'ち', 'ち', 'つ', 'つ', 'つ', 'つ', 'つ', 'て', 'て', 'て', 'て',
+    'て', 'て', 'て', 'て', 'て', 'と', 'と', 'と', 'と', 'と', 'と', 'と', 'な', 'な', 'な', 'な',
+    'な', 'な', 'な', 'な', 'な', 'に', 'に', 'に', 'に', 'に', 'に', 'に', 'に', 'ぬ', 'ぬ', 'ぬ',
+    'ね', 'ね', 'ね', 'ね', 'ね', 'ね', 'ね', 'の', 'の', 'の', 'の', 'の', 'は', 'は', 'は', 'は',
+    'は', 'は', 'は', 'は', 'は', 'は', 'は', 'ひ', 'ひ', 'ひ', 'ひ', 'ひ', 'ひ', 'ひ', 'ふ', 'ふ',
+    'ふ', 'へ', 'へ', 'へ', 'へ', 'へ', 'へ', 'へ', 'ほ', 'ほ', 'ほ', 'ほ', 'ほ', 'ほ', 'ほ', 'ほ',
+    'ま', 'ま', 'ま', 'ま', 'ま', 'ま', 'ま', 'み', 'み', 'み', 'み', 'み', 'み', 'み', 'む', 'む',
+    'む', 'む', 'め', 'め', 'め', 'も', 'も', 'も', 'も', 'も', 'も', 'や', 'や', 'や', 'や', 'や',
+    'や', 'ゆ', 'ゆ', 'ゆ', 'ゆ', 'よ', 'よ', 'よ', 'よ', 'よ', 'よ', 'ら', 'ら', 'ら', 'ら', 'り',
+    'り', 'り', 'り', 'り', 'り', 'り', 'る', 'る', 'る', 'る', 'る', 'る', 'れ', 'れ', 'れ', 'れ',
+    'ろ', 'ろ', 'ろ', 'ろ', 'ろ', 'ろ', 'わ', 'わ', 'わ', 'わ', 'わ', 'ゐ', 'ゐ', 'ゐ', 'ゐ', 'ゐ',
+    'ゑ', 'ゑ', 'ゑ', 'ゑ', 'を', 'を', 'を', 'を', 'を', 'を', 'を', 'ん', 'ん',
+];
+
+fn to_hiragana(char: char) -> char {
+    HIRAGANA[(char as u32 - HENTAIGANA[0]) as usize]
+}
+
+/// Convert [Hentaigana](https://en.wikipedia.org/wiki/Hentaigana) to the modern hiragana they
+/// are read as
+///
+/// Hentaigana with several readings, like 𛀅 (a or wo), become the hiragana of the first one.
+/// Passes through any other chars.
+///
+/// # Examples
+///
+/// hentaigana_to_hiragana('𛂇𛂺𛁗')
+///
+/// // => "にほそ"
+///
+/// hentaigana_to_hiragana('𛀅')
+///
+/// // => "あ"
+pub fn hentaigana_to_hiragana(input: &str) -> Cow<'_, str> {
+    if !input.chars().any(is_char_hentaigana) {
+        return Cow::Borrowed(input);
+    }
+    input
+        .chars()
+        .map(|char| {
+            if is_char_hentaigana(char) {
+                to_hiragana(char)
+            } else {
+                char
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_hentaigana() {
+        assert_eq!(hentaigana_to_hiragana("\u{1B002}\u{1B11E}"), "あん");
+        assert_eq!(hentaigana_to_hiragana("𛂇𛂺𛁗"), "にほそ");
+        assert_eq!(hentaigana_to_hiragana("𛀙𛂦"), "かは");
+        assert_eq!(hentaigana_to_hiragana("𛀅"), "あ");
+        assert_eq!(hentaigana_to_hiragana("𛄚𛄝"), "をん");
+    }
+    #[test]
+    fn passes_other_chars_through() {
+        assert_eq!(hentaigana_to_hiragana("ひらがな 漢字"), "ひらがな 漢字");
+        assert_eq!(
+            hentaigana_to_hiragana("\u{1B001}\u{1B11F}"),
+            "\u{1B001}\u{1B11F}"
+        );
+        assert!(matches!(hentaigana_to_hiragana("かな"), Cow::Borrowed(_)));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::constants::{HIRAGANA_START, KATAKANA_START};
+use crate::utils::is_char_hentaigana::*;
 use crate::utils::is_char_hiragana::*;
 use crate::utils::is_char_long_dash::*;
 use crate::utils::is_char_slash_dot::*;
@@ -17,18 +18,27 @@ use crate::utils::is_char_slash_dot::*;
 /// hiragana_to_katakana('ひらがな is a type of kana')
 ///
 /// // => "ヒラガナ is a type of kana"
-
 pub fn hiragana_to_katakana(input: &str) -> String {
     let mut kata = vec![];
     for char in input.chars() {
-        // Short circuit to avoid incorrect codeshift for 'ー' and '・'
-        if is_char_long_dash(char) || is_char_slash_dot(char) {
+        // Short circuit to avoid incorrect codeshift for 'ー', '・' and hentaigana, which have no
+        // katakana
+        if is_char_long_dash(char) || is_char_slash_dot(char) || is_char_hentaigana(char) {
             kata.push(char);
         } else if is_char_hiragana(char) {
-            // Shift charcode.
-            let code = char as i32 + (KATAKANA_START as i32 - HIRAGANA_START as i32);
-            // let kata_char = String.from_char_code(code);
-            let kata_char = std::char::from_u32(code as u32).unwrap();
+            let kata_char = match char {
+                // archaic ye and wu, small ko, wi, we and wo
+                '\u{1B001}' => '\u{1B121}',
+                '\u{1B11F}' => '\u{1B122}',
+                '\u{1B132}' => '\u{1B155}',
+                '\u{1B150}'..='\u{1B152}' => std::char::from_u32(char as u32 + 0x14).unwrap(),
+                _ => {
+                    // Shift charcode.
+                    let code = char as i32 + (KATAKANA_START as i32 - HIRAGANA_START as i32);
+                    // let kata_char = String.from_char_code(code);
+                    std::char::from_u32(code as u32).unwrap()
+                }
+            };
             kata.push(kata_char);
         } else {
             // Pass non-hiragana chars through
@@ -37,3 +47,13 @@ pub fn hiragana_to_katakana(input: &str) -> String {
     }
     kata.into_iter().collect()
 }
+
+#[test]
+fn hiragana_to_katakana_test() {
+    assert_eq!(hiragana_to_katakana("ひらがな"), "ヒラガナ");
+    assert_eq!(hiragana_to_katakana("𛂇𛂺ん"), "𛂇𛂺ン");
+    assert_eq!(
+        hiragana_to_katakana("\u{1B132}\u{1B152}\u{1B001}"),
+        "\u{1B155}\u{1B166}\u{1B121}"
+    );
+}
@@ -0,0 +1,258 @@
+use std::borrow::Cow;
+
+use crate::utils::is_char_hiragana::is_char_hiragana;
+use crate::utils::is_char_kana::is_char_kana;
+use crate::utils::is_char_kanji::is_char_kanji;
+
+/// Kana which likely end a word if they don't start one: particles and the ending of attributive
+/// adjectives (よき)
+const WORD_ENDINGS: [char; 5] = ['の', 'は', 'を', 'に', 'き'];
+
+/// Kana of the え-row and their い-row counterparts, which read as ょう before う or ふ
+const E_ROW: [(char, char); 12] = [
+    ('け', 'き'),
+    ('げ', 'ぎ'),
+    ('せ', 'し'),
+    ('ぜ', 'じ'),
+    ('て', 'ち'),
+    ('で', 'じ'),
+    ('ね', 'に'),
+    ('へ', 'ひ'),
+    ('べ', 'び'),
+    ('ぺ', 'ぴ'),
+    ('め', 'み'),
+    ('れ', 'り'),
+];
+
+/// Kana of the い-row (without い) and their modern spellings, which read as ゅう before う or ふ
+/// and as ょう before やう
+const I_ROW: [(char, char); 12] = [
+    ('き', 'き'),
+    ('ぎ', 'ぎ'),
+    ('し', 'し'),
+    ('じ', 'じ'),
+    ('ち', 'ち'),
+    ('ぢ', 'じ'),
+    ('に', 'に'),
+    ('ひ', 'ひ'),
+    ('び', 'び'),
+    ('ぴ', 'ぴ'),
+    ('み', 'み'),
+    ('り', 'り'),
+];
+
+/// Kana of the あ-row and their お-row counterparts, which read as a long o before う
+const A_ROW: [(char, char); 15] = [
+    ('あ', 'お'),
+    ('か', 'こ'),
+    ('が', 'ご'),
+    ('さ', 'そ'),
+    ('ざ', 'ぞ'),
+    ('た', 'と'),
+    ('だ', 'ど'),
+    ('な', 'の'),
+    ('は', 'ほ'),
+    ('ば', 'ぼ'),
+    ('ぱ', 'ぽ'),
+    ('ま', 'も'),
+    ('や', 'よ'),
+    ('ら', 'ろ'),
+    ('わ', 'お'),
+];
+
+fn lookup(table: &[(char, char)], kana: char) -> Option<char> {
+    table
+        .iter()
+        .find(|(historical, _)| *historical == kana)
+        .map(|(_, modern)| *modern)
+}
+
+/// Converts the kana at the start of `chars`, returning the modern kana and the number of chars
+/// they replace.
+fn convert_next(
+    chars: &[char],
+    prev: Option<char>,
+    follows_word_end: bool,
+) -> (Cow<'static, str>, usize) {
+    let is_medial = prev.is_some_and(is_char_hiragana) && !follows_word_end;
+    let is_initial = !prev.is_some_and(|prev| is_char_kana(prev) || is_char_kanji(prev));
+    let kana = chars[0];
+    let next = chars.get(1).copied();
+    let after_next = chars.get(2).copied();
+    let modern = |kana: char, rest: &str| Cow::Owned(format!("{kana}{rest}"));
+    match (kana, next, after_next) {
+        ('く', Some('わ' | 'ゎ'), _) => (Cow::Borrowed("か"), 2),
+        ('ぐ', Some('わ' | 'ゎ'), _) => (Cow::Borrowed("が"), 2),
+        ('え' | 'ゑ', Some('う' | 'ふ'), _) => (Cow::Borrowed("よう"), 2),
+        (_, Some('う' | 'ふ'), _) if lookup(&E_ROW, kana).is_some() => {
+            (modern(lookup(&E_ROW, kana).unwrap(), "ょう"), 2)
+        }
+        (_, Some('や'), Some('う' | 'ふ')) if lookup(&I_ROW, kana).is_some() => {
+            (modern(lookup(&I_ROW, kana).unwrap(), "ょう"), 3)
+        }
+        (_, Some('う' | 'ふ'), _) if lookup(&I_ROW, kana).is_some() => {
+            (modern(lookup(&I_ROW, kana).unwrap(), "ゅう"), 2)
+        }
+        // Only a written う lengthens the o, verbs like かふ (買ふ) keep their vowel
+        (_, Some('う'), _) if lookup(&A_ROW, kana).is_some() => {
+            (modern(lookup(&A_ROW, kana).unwrap(), "う"), 2)
+        }
+        // A ふ inside of a word reads as う and lengthens the o alike (あふぎ → おうぎ)
+        (_, Some('ふ'), Some(after))
+            if is_char_hiragana(after) && lookup(&A_ROW, kana).is_some() =>
+        {
+            (modern(lookup(&A_ROW, kana).unwrap(), "う"), 2)
+        }
+        ('ゐ', _, _) => (Cow::Borrowed("い"), 1),
+        ('ゑ', _, _) => (Cow::Borrowed("え"), 1),
+        ('ヰ', _, _) => (Cow::Borrowed("イ"), 1),
+        ('ヱ', _, _) => (Cow::Borrowed("エ"), 1),
+        // を after a kanji or another kana may be the particle
+        ('を', Some(next), _) if is_initial && is_char_hiragana(next) => (Cow::Borrowed("お"), 1),
+        ('ひ', _, _) if is_medial => (Cow::Borrowed("い"), 1),
+        ('ふ', _, _) if is_medial => (Cow::Borrowed("う"), 1),
+        ('ほ', _, _) if is_medial => (Cow::Borrowed("お"), 1),
+        // は and へ at the end of a word are particles
+        ('は', Some(next), _) if is_medial && is_char_hiragana(next) => (Cow::Borrowed("わ"), 1),
+        ('へ', Some(next), _) if is_medial && is_char_hiragana(next) => (Cow::Borrowed("え"), 1),
+        _ => (Cow::Owned(kana.to_string()), 1),
+    }
+}
+
+/// Converts `input`, pushing the offset in `input` of each char of the output to `offsets`, in
+/// chars.
+pub(crate) fn historical_kana_to_modern_kana_with_offsets(
+    input: &str,
+    offsets: &mut Vec<usize>,
+) -> String {
+    let chars = input.chars().collect::<Vec<_>>();
+    let mut output = String::with_capacity(input.len());
+    let mut pos = 0;
+    // Whether the previous kana were contracted to a long vowel, which likely ends a word (けふ)
+    let mut follows_long_vowel = false;
+    // Whether the previous kana was kept as written
+    let mut prev_kept = false;
+    while pos < chars.len() {
+        let prev = pos.checked_sub(1).map(|prev| chars[prev]);
+        // A word of a single kana after the ending is unlikely (ほのほ)
+        let follows_ending = prev_kept
+            && pos >= 2
+            && WORD_ENDINGS.contains(&chars[pos - 1])
+            && (is_char_kana(chars[pos - 2]) || is_char_kanji(chars[pos - 2]))
+            && chars.get(pos + 1).is_some_and(|c| is_char_hiragana(*c));
+        let (modern, len) = convert_next(&chars[pos..], prev, follows_long_vowel || follows_ending);
+        offsets.extend(std::iter::repeat_n(pos, modern.chars().count()));
+        follows_long_vowel = len > 1;
+        prev_kept = len == 1 && modern.chars().eq([chars[pos]]);
+        output.push_str(&modern);
+        pos += len;
+    }
+    output
+}
+
+/// Convert hiragana in historical kana orthography (rekishiteki kanazukai) to modern kana usage
+///
+/// The rules cover the spellings of long vowels (けふ → きょう, てふ → ちょう, かう → こう,
+/// きやう → きょう, しう → しゅう), the obsolete kana (ゐ → い, ゑ → え, also in katakana),
+/// くわ and ぐわ (→ か, が) and the は-row inside of words, which is read like the わ-row
+/// (かはる → かわる, いふ → いう, おもひ → おもい, あふぎ → おうぎ). を at the start of a word
+/// reads as お (をとこ → おとこ). Without knowing the words they are a heuristic: は and へ are
+/// only read as わ and え if more kana follow, so that the particles are kept, verbs ending in ふ
+/// keep their vowel (かふ → かう) and を is kept after kana or kanji, where it may be the
+/// particle. The は-row is kept after kana which likely end a word, i.e. particles like の and
+/// に, the adjective ending き and long vowels (かはのほとり → かわのほとり, けふは → きょうは), at
+/// the cost of words like さきはひ.
+///
+/// # Examples
+/// ```
+/// use wana_kana::utils::historical_kana_to_modern_kana;
+/// assert_eq!(historical_kana_to_modern_kana("てふてふ"), "ちょうちょう");
+/// assert_eq!(historical_kana_to_modern_kana("けふはゐる"), "きょうはいる");
+/// ```
+pub fn historical_kana_to_modern_kana(input: &str) -> String {
+    historical_kana_to_modern_kana_with_offsets(input, &mut Vec::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sane_defaults() {
+        assert_eq!(historical_kana_to_modern_kana(""), "");
+        assert_eq!(
+            historical_kana_to_modern_kana("ひらがな カタカナ 漢字"),
+            "ひらがな カタカナ 漢字"
+        );
+    }
+    #[test]
+    fn long_vowels() {
+        assert_eq!(historical_kana_to_modern_kana("けふ"), "きょう");
+        assert_eq!(historical_kana_to_modern_kana("てふてふ"), "ちょうちょう");
+        assert_eq!(historical_kana_to_modern_kana("せうせつ"), "しょうせつ");
+        assert_eq!(historical_kana_to_modern_kana("えふ"), "よう");
+        assert_eq!(historical_kana_to_modern_kana("きやう"), "きょう");
+        assert_eq!(historical_kana_to_modern_kana("やうやう"), "ようよう");
+        assert_eq!(historical_kana_to_modern_kana("かうがい"), "こうがい");
+        assert_eq!(historical_kana_to_modern_kana("まうす"), "もうす");
+        assert_eq!(historical_kana_to_modern_kana("じふ"), "じゅう");
+        assert_eq!(historical_kana_to_modern_kana("ぢう"), "じゅう");
+    }
+    #[test]
+    fn obsolete_kana() {
+        assert_eq!(historical_kana_to_modern_kana("ゐる"), "いる");
+        assert_eq!(historical_kana_to_modern_kana("こゑ"), "こえ");
+        assert_eq!(historical_kana_to_modern_kana("ヰスキー"), "イスキー");
+        assert_eq!(historical_kana_to_modern_kana("をとこ"), "おとこ");
+        assert_eq!(historical_kana_to_modern_kana("本をよむ"), "本をよむ");
+        assert_eq!(historical_kana_to_modern_kana("これを"), "これを");
+    }
+    #[test]
+    fn kwa() {
+        assert_eq!(historical_kana_to_modern_kana("くわし"), "かし");
+        assert_eq!(historical_kana_to_modern_kana("ぐゎつ"), "がつ");
+    }
+    #[test]
+    fn medial_ha_row() {
+        assert_eq!(historical_kana_to_modern_kana("かはる"), "かわる");
+        assert_eq!(historical_kana_to_modern_kana("いふ"), "いう");
+        assert_eq!(historical_kana_to_modern_kana("かふ"), "かう");
+        assert_eq!(historical_kana_to_modern_kana("あふぎ"), "おうぎ");
+        assert_eq!(historical_kana_to_modern_kana("たふとし"), "とうとし");
+        assert_eq!(historical_kana_to_modern_kana("おもひ"), "おもい");
+        assert_eq!(historical_kana_to_modern_kana("かへる"), "かえる");
+        assert_eq!(historical_kana_to_modern_kana("ほのほ"), "ほのお");
+        assert_eq!(historical_kana_to_modern_kana("はは"), "はは");
+        assert_eq!(historical_kana_to_modern_kana("これは山へ"), "これは山へ");
+        assert_eq!(historical_kana_to_modern_kana("花は"), "花は");
+    }
+    #[test]
+    fn ha_row_after_word_endings() {
+        assert_eq!(historical_kana_to_modern_kana("けふはゐる"), "きょうはいる");
+        assert_eq!(
+            historical_kana_to_modern_kana("うつくしきはな"),
+            "うつくしきはな"
+        );
+        assert_eq!(
+            historical_kana_to_modern_kana("かはのほとり"),
+            "かわのほとり"
+        );
+        assert_eq!(
+            historical_kana_to_modern_kana("ひとにはなす"),
+            "ひとにはなす"
+        );
+        assert_eq!(historical_kana_to_modern_kana("てふはとぶ"), "ちょうはとぶ");
+        assert_eq!(historical_kana_to_modern_kana("にほひ"), "におい");
+        assert_eq!(historical_kana_to_modern_kana("きはめて"), "きわめて");
+        assert_eq!(historical_kana_to_modern_kana("いはひ"), "いわい");
+    }
+    #[test]
+    fn offsets() {
+        let mut offsets = Vec::new();
+        assert_eq!(
+            historical_kana_to_modern_kana_with_offsets("けふ山", &mut offsets),
+            "きょう山"
+        );
+        assert_eq!(offsets, [0, 0, 0, 2]);
+    }
+}
@@ -1,7 +1,6 @@
 /// Tests a character and an english consonant. Returns true if the char is a consonant.
 ///
 /// * `include_Y` include y as a consonant in test
-
 #[inline]
 pub fn is_char_consonant(char: char, include_y: bool) -> bool {
     match char {
@@ -0,0 +1,18 @@
+use crate::constants::HANKAKU_KATAKANA;
+use crate::utils::is_char_in_range::*;
+
+/// Tests a character. Returns true if the character is halfwidth
+/// [Katakana](https://en.wikipedia.org/wiki/Half-width_kana), including ｰ and the voicing marks ﾞ
+/// and ﾟ.
+pub fn is_char_halfwidth_katakana(char: char) -> bool {
+    is_char_in_range(char, HANKAKU_KATAKANA[0], HANKAKU_KATAKANA[1])
+}
+
+#[test]
+fn is_char_halfwidth_katakana_test() {
+    assert!(is_char_halfwidth_katakana('ｶ'));
+    assert!(is_char_halfwidth_katakana('ﾞ'));
+    assert!(!is_char_halfwidth_katakana('カ'));
+    assert!(!is_char_halfwidth_katakana('｡'));
+    assert!(!is_char_halfwidth_katakana('n'));
+}
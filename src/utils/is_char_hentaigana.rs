@@ -0,0 +1,19 @@
+use crate::constants::HENTAIGANA;
+use crate::utils::is_char_in_range::*;
+
+/// Tests a character. Returns true if the character is
+/// [Hentaigana](https://en.wikipedia.org/wiki/Hentaigana), the obsolete variants of hiragana.
+pub fn is_char_hentaigana(char: char) -> bool {
+    is_char_in_range(char, HENTAIGANA[0], HENTAIGANA[1])
+}
+
+#[test]
+fn is_char_hentaigana_test() {
+    assert!(is_char_hentaigana('\u{1B002}'));
+    assert!(is_char_hentaigana('𛂇'));
+    assert!(is_char_hentaigana('\u{1B11E}'));
+    assert!(!is_char_hentaigana('\u{1B001}'));
+    assert!(!is_char_hentaigana('\u{1B11F}'));
+    assert!(!is_char_hentaigana('に'));
+    assert!(!is_char_hentaigana('n'));
+}
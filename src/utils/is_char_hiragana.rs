@@ -1,18 +1,31 @@
 use crate::constants::{HIRAGANA_END, HIRAGANA_START};
+use crate::utils::is_char_hentaigana::is_char_hentaigana;
 use crate::utils::is_char_in_range::*;
 use crate::utils::is_char_long_dash::is_char_long_dash;
 
-/// Tests a character. Returns true if the character is [Hiragana](https://en.wikipedia.org/wiki/Hiragana).
+/// Tests a character. Returns true if the character is [Hiragana](https://en.wikipedia.org/wiki/Hiragana), including hentaigana and the archaic and small hiragana of the supplementary kana blocks.
 pub fn is_char_hiragana(char: char) -> bool {
     if is_char_long_dash(char) {
         return true;
     };
     is_char_in_range(char, HIRAGANA_START, HIRAGANA_END)
+        || is_char_hentaigana(char)
+        || matches!(
+            char,
+            // archaic ye and wu
+            '\u{1B001}' | '\u{1B11F}'
+            // small こ, ゐ, ゑ and を
+            | '\u{1B132}' | '\u{1B150}'..='\u{1B152}'
+        )
 }
 
 #[test]
 fn is_char_hiragana_test() {
     assert!(is_char_hiragana('な'));
+    assert!(is_char_hiragana('𛂇'));
+    assert!(is_char_hiragana('\u{1B001}'));
+    assert!(is_char_hiragana('\u{1B132}'));
+    assert!(!is_char_hiragana('\u{1B155}'));
     assert!(!is_char_hiragana('ナ'));
     assert!(!is_char_hiragana('n'));
     assert!(!is_char_hiragana('!'));
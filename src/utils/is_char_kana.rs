@@ -1,10 +1,10 @@
+use crate::utils::is_char_halfwidth_katakana::*;
 use crate::utils::is_char_hiragana::*;
 use crate::utils::is_char_katakana::*;
 
-/// Tests a character. Returns true if the character is [Hiragana](https://en.wikipedia.org/wiki/Hiragana) or [Katakana](https://en.wikipedia.org/wiki/Katakana).
-
+/// Tests a character. Returns true if the character is [Hiragana](https://en.wikipedia.org/wiki/Hiragana) or [Katakana](https://en.wikipedia.org/wiki/Katakana), including halfwidth katakana and hentaigana.
 pub fn is_char_kana(char: char) -> bool {
-    is_char_hiragana(char) || is_char_katakana(char)
+    is_char_hiragana(char) || is_char_katakana(char) || is_char_halfwidth_katakana(char)
 }
 
 #[test]
@@ -15,4 +15,6 @@ fn is_char_kana_test() {
     assert!(!is_char_kana('!'));
     assert!(!is_char_kana('-'));
     assert!(is_char_kana('ー'));
+    assert!(is_char_kana('ｶ'));
+    assert!(is_char_kana('𛂇'));
 }
@@ -1,15 +1,34 @@
-use crate::constants::{KATAKANA_END, KATAKANA_START};
+use crate::constants::{KATAKANA_END, KATAKANA_PHONETIC_EXTENSIONS, KATAKANA_START};
 use crate::utils::is_char_in_range::*;
 
-/// Tests a character. Returns true if the character is [Katakana](https://en.wikipedia.org/wiki/Katakana).
-
+/// Tests a character. Returns true if the character is [Katakana](https://en.wikipedia.org/wiki/Katakana), including the small katakana used for Ainu and the archaic, small and Taiwanese katakana of the supplementary kana blocks.
 pub fn is_char_katakana(char: char) -> bool {
     is_char_in_range(char, KATAKANA_START, KATAKANA_END)
+        || is_char_in_range(
+            char,
+            KATAKANA_PHONETIC_EXTENSIONS[0],
+            KATAKANA_PHONETIC_EXTENSIONS[1],
+        )
+        || matches!(
+            char,
+            // Taiwanese tone marks
+            '\u{1AFF0}'..='\u{1AFF3}' | '\u{1AFF5}'..='\u{1AFFB}' | '\u{1AFFD}' | '\u{1AFFE}'
+            // archaic e, yi, ye and wu
+            | '\u{1B000}' | '\u{1B120}'..='\u{1B122}'
+            // small コ, ヰ, ヱ, ヲ and ン
+            | '\u{1B155}' | '\u{1B164}'..='\u{1B167}'
+        )
 }
 
 #[test]
 fn is_char_katakana_test() {
     assert!(is_char_katakana('ナ'));
+    assert!(is_char_katakana('ㇰ'));
+    assert!(is_char_katakana('ㇿ'));
+    assert!(is_char_katakana('\u{1AFF0}'));
+    assert!(is_char_katakana('\u{1B167}'));
+    assert!(!is_char_katakana('\u{1AFF4}'));
+    assert!(!is_char_katakana('\u{1B132}'));
     assert!(!is_char_katakana('は'));
     assert!(!is_char_katakana('n'));
     assert!(!is_char_katakana('!'));
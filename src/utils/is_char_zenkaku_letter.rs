@@ -0,0 +1,35 @@
+use crate::constants::{ZENKAKU_ASCII, ZENKAKU_ASCII_OFFSET, ZENKAKU_LOWERCASE, ZENKAKU_UPPERCASE};
+use crate::utils::is_char_in_range::*;
+
+/// Tests a character. Returns true if the character is a Zenkaku latin letter(Ａ-Ｚ, ａ-ｚ)
+pub fn is_char_zenkaku_letter(char: char) -> bool {
+    is_char_in_range(char, ZENKAKU_UPPERCASE[0], ZENKAKU_UPPERCASE[1])
+        || is_char_in_range(char, ZENKAKU_LOWERCASE[0], ZENKAKU_LOWERCASE[1])
+}
+
+/// Returns the ASCII char for a Zenkaku letter, number, punctuation mark or space (ａ, １, ！,
+/// '　'), and any other char unchanged.
+pub fn zenkaku_to_ascii(char: char) -> char {
+    if char == '\u{3000}' {
+        ' '
+    } else if is_char_in_range(char, ZENKAKU_ASCII[0], ZENKAKU_ASCII[1]) {
+        char::from_u32(char as u32 - ZENKAKU_ASCII_OFFSET).unwrap_or(char)
+    } else {
+        char
+    }
+}
+
+#[test]
+fn is_char_zenkaku_letter_test() {
+    assert!(is_char_zenkaku_letter('ａ'));
+    assert!(is_char_zenkaku_letter('Ｚ'));
+    assert!(!is_char_zenkaku_letter('a'));
+    assert!(!is_char_zenkaku_letter('１'));
+    assert!(!is_char_zenkaku_letter('！'));
+    assert_eq!(zenkaku_to_ascii('Ｋ'), 'K');
+    assert_eq!(zenkaku_to_ascii('ｋ'), 'k');
+    assert_eq!(zenkaku_to_ascii('１'), '1');
+    assert_eq!(zenkaku_to_ascii('＇'), '\'');
+    assert_eq!(zenkaku_to_ascii('　'), ' ');
+    assert_eq!(zenkaku_to_ascii('か'), 'か');
+}
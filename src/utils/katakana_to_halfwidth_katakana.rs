@@ -0,0 +1,106 @@
+use crate::constants::{KANA_PUNCTUATION, ZENKAKU_ASCII, ZENKAKU_ASCII_OFFSET};
+use crate::utils::halfwidth_katakana_to_katakana::{
+    FULLWIDTH, HALFWIDTH_SEMI_VOICED_MARK, HALFWIDTH_VOICED_MARK,
+};
+use crate::utils::is_char_in_range::is_char_in_range;
+
+/// Returns the halfwidth form of a katakana or punctuation char.
+fn to_halfwidth(char: char) -> Option<char> {
+    if is_char_in_range(char, ZENKAKU_ASCII[0], ZENKAKU_ASCII[1]) {
+        return char::from_u32(char as u32 - ZENKAKU_ASCII_OFFSET);
+    }
+    let index = FULLWIDTH.iter().position(|fullwidth| *fullwidth == char)?;
+    char::from_u32(KANA_PUNCTUATION[0] + index as u32)
+}
+
+/// Splits a (semi-)voiced katakana into its base katakana and voicing mark.
+fn remove_voicing_mark(kana: char) -> Option<(char, char)> {
+    let shift = |offset: u32| char::from_u32(kana as u32 - offset);
+    match kana {
+        _ if "ガギグゲゴザジズゼゾダヂヅデドバビブベボ".contains(kana) => {
+            Some((shift(1)?, HALFWIDTH_VOICED_MARK))
+        }
+        _ if "パピプペポ".contains(kana) => Some((shift(2)?, HALFWIDTH_SEMI_VOICED_MARK)),
+        'ヴ' => Some(('ウ', HALFWIDTH_VOICED_MARK)),
+        'ヷ' => Some(('ワ', HALFWIDTH_VOICED_MARK)),
+        'ヺ' => Some(('ヲ', HALFWIDTH_VOICED_MARK)),
+        _ => None,
+    }
+}
+
+/// Convert [Katakana](https://en.wikipedia.org/wiki/Katakana) and Japanese punctuation to their
+/// [halfwidth](https://en.wikipedia.org/wiki/Half-width_kana) forms
+///
+/// Fullwidth ASCII punctuation (！, ？, （, ）, …) becomes ASCII.
+/// Voiced and semi-voiced katakana are split into the base katakana and ﾞ or ﾟ. Passes through any
+/// chars without a halfwidth form, such as ヮ, ヵ, ヶ, ヰ and ヱ.
+///
+/// # Examples
+///
+/// katakana_to_halfwidth_katakana('ガソリン')
+///
+/// // => "ｶﾞｿﾘﾝ"
+///
+/// katakana_to_halfwidth_katakana('パーティー！')
+///
+/// // => "ﾊﾟｰﾃｨｰ!"
+pub fn katakana_to_halfwidth_katakana(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    for char in input.chars() {
+        if let Some(halfwidth) = to_halfwidth(char) {
+            output.push(halfwidth);
+        } else if let Some((base, mark)) = remove_voicing_mark(char) {
+            output.extend(to_halfwidth(base));
+            output.push(mark);
+        } else {
+            output.push(char);
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::halfwidth_katakana_to_katakana::halfwidth_katakana_to_katakana;
+
+    #[test]
+    fn converts_katakana() {
+        assert_eq!(
+            katakana_to_halfwidth_katakana("アイウエオ ァィゥェォ ャュョッー ヲン"),
+            "ｱｲｳｴｵ ｧｨｩｪｫ ｬｭｮｯｰ ｦﾝ"
+        );
+        assert_eq!(
+            katakana_to_halfwidth_katakana("「カタカナ」、デス。"),
+            "｢ｶﾀｶﾅ｣､ﾃﾞｽ｡"
+        );
+    }
+    #[test]
+    fn splits_voicing_marks() {
+        assert_eq!(katakana_to_halfwidth_katakana("ガソリン"), "ｶﾞｿﾘﾝ");
+        assert_eq!(katakana_to_halfwidth_katakana("バピブペボ"), "ﾊﾞﾋﾟﾌﾞﾍﾟﾎﾞ");
+        assert_eq!(katakana_to_halfwidth_katakana("ヴァヷヺ"), "ｳﾞｧﾜﾞｦﾞ");
+    }
+    #[test]
+    fn converts_fullwidth_punctuation() {
+        assert_eq!(
+            katakana_to_halfwidth_katakana("パーティー！（ヤ＆ユ）？"),
+            "ﾊﾟｰﾃｨｰ!(ﾔ&ﾕ)?"
+        );
+    }
+    #[test]
+    fn passes_other_chars_through() {
+        assert_eq!(
+            katakana_to_halfwidth_katakana("ヮヵヶヰヱ ひらがな"),
+            "ヮヵヶヰヱ ひらがな"
+        );
+    }
+    #[test]
+    fn round_trips() {
+        let katakana = "ガッコウ・ヴァイオリン・パーティー";
+        assert_eq!(
+            halfwidth_katakana_to_katakana(&katakana_to_halfwidth_katakana(katakana)),
+            katakana
+        );
+    }
+}
@@ -11,11 +11,16 @@
 //! katakana_to_hiragana('カタカナ is a type of kana')
 //!
 //! // => "かたかな is a type of kana"
+//!
+//! katakana_to_hiragana('ｶﾞｿﾘﾝ')
+//!
+//! // => "がそりん"
 
 use fnv::FnvHashMap;
 
 use crate::constants::{HIRAGANA_START, KATAKANA_START};
 use crate::to_romaji::TO_ROMAJI_NODE_TREE;
+use crate::utils::halfwidth_katakana_to_katakana::*;
 use crate::utils::is_char_katakana::*;
 use crate::utils::is_char_long_dash::*;
 use crate::utils::is_char_slash_dot::*;
@@ -41,10 +46,16 @@ lazy_static! {
 }
 
 pub fn katakana_to_hiragana(input: &str) -> String {
-    katakana_to_hiragana_with_opt(input, false)
+    katakana_to_hiragana_with_opt(input, false, true)
 }
 
-pub(crate) fn katakana_to_hiragana_with_opt(input: &str, is_destination_romaji: bool) -> String {
+/// * `convert_long_vowel_mark` - set to false to keep 'ー' instead of turning it into the vowel
+pub(crate) fn katakana_to_hiragana_with_opt(
+    input: &str,
+    is_destination_romaji: bool,
+    convert_long_vowel_mark: bool,
+) -> String {
+    let input = &*halfwidth_katakana_to_katakana(input);
     let mut hira = Vec::with_capacity(input.chars().count());
     let mut previous_kana: Option<char> = None;
     for (index, input_char) in input.chars().enumerate() {
@@ -55,9 +66,10 @@ pub(crate) fn katakana_to_hiragana_with_opt(input: &str, is_destination_romaji:
         {
             hira.push(input_char);
         // Transform long vowels: 'オー' to 'おう'
-        } else if let (Some(previous_kana), true) =
-            (previous_kana, is_char_inner_long_dash(input_char, index))
-        {
+        } else if let (Some(previous_kana), true) = (
+            previous_kana,
+            convert_long_vowel_mark && is_char_inner_long_dash(input_char, index),
+        ) {
             // Transform previous_kana back to romaji, and slice off the vowel
             let Some(node) = TO_ROMAJI_NODE_TREE.find_transition_node(previous_kana) else {
                 hira.push(input_char);
@@ -83,6 +95,16 @@ pub(crate) fn katakana_to_hiragana_with_opt(input: &str, is_destination_romaji:
                 'ヸ' => 'ゐ', // wi with a voiced mark
                 'ヹ' => 'ゑ', // we with a voiced mark
                 'ヺ' => 'を', // wo with a voiced mark
+                // archaic ye and wu, small ko, wi, we and wo
+                '\u{1B121}' => '\u{1B001}',
+                '\u{1B122}' => '\u{1B11F}',
+                '\u{1B155}' => '\u{1B132}',
+                '\u{1B164}'..='\u{1B166}' => {
+                    std::char::from_u32(input_char as u32 - 0x14).unwrap_or(input_char)
+                }
+                // the small katakana for Ainu and the other supplementary katakana have no
+                // hiragana
+                '\u{31F0}'..='\u{31FF}' | '\u{1AFF0}'..='\u{1B16F}' => input_char,
                 _ => {
                     // Shift charcode.
                     let code = input_char as i32 + (HIRAGANA_START as i32 - KATAKANA_START as i32);
@@ -109,4 +131,10 @@ fn test_katakana_to_hiragana() {
         katakana_to_hiragana("カタカナ is a type of kana"),
         "かたかな is a type of kana"
     );
+    assert_eq!(katakana_to_hiragana("ｶﾞｿﾘﾝ ｽｰﾊﾟｰ"), "がそりん すうぱあ");
+    assert_eq!(katakana_to_hiragana("アイヌ イタㇰ"), "あいぬ いたㇰ");
+    assert_eq!(
+        katakana_to_hiragana("\u{1B155}\u{1B164}\u{1B167}\u{1B000}"),
+        "\u{1B132}\u{1B150}\u{1B167}\u{1B000}"
+    );
 }
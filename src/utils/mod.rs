@@ -1,9 +1,15 @@
 #[macro_use]
 pub(crate) mod hashmap_macro;
+pub mod expand_iteration_marks;
 pub mod get_chunk;
+pub mod halfwidth_katakana_to_katakana;
+pub mod hentaigana_to_hiragana;
 pub mod hiragana_to_katakana;
+pub mod historical_kana_to_modern_kana;
 pub mod is_char_consonant;
 pub mod is_char_english_punctuation;
+pub mod is_char_halfwidth_katakana;
+pub mod is_char_hentaigana;
 pub mod is_char_hiragana;
 pub mod is_char_in_range;
 pub mod is_char_japanese;
@@ -19,13 +25,21 @@ pub mod is_char_romaji;
 pub mod is_char_slash_dot;
 pub mod is_char_upper_case;
 pub mod is_char_vowel;
+pub mod is_char_zenkaku_letter;
+pub mod katakana_to_halfwidth_katakana;
 pub mod katakana_to_hiragana;
 pub mod romaji_to_hiragana;
 
+pub use expand_iteration_marks::*;
 pub use get_chunk::*;
+pub use halfwidth_katakana_to_katakana::*;
+pub use hentaigana_to_hiragana::*;
 pub use hiragana_to_katakana::*;
+pub use historical_kana_to_modern_kana::*;
 pub use is_char_consonant::*;
 pub use is_char_english_punctuation::*;
+pub use is_char_halfwidth_katakana::*;
+pub use is_char_hentaigana::*;
 pub use is_char_hiragana::*;
 pub use is_char_in_range::*;
 pub use is_char_japanese::*;
@@ -41,6 +55,8 @@ pub use is_char_romaji::*;
 pub use is_char_slash_dot::*;
 pub use is_char_upper_case::*;
 pub use is_char_vowel::*;
+pub use is_char_zenkaku_letter::*;
+pub use katakana_to_halfwidth_katakana::*;
 pub use katakana_to_hiragana::*;
 pub use romaji_to_hiragana::*;
 
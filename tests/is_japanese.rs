@@ -73,4 +73,52 @@ mod tests {
         assert!(
             "＃ＭｅＴｏｏ、これを前に「ＫＵＲＯＳＨＩＯ」は、都内で報道陣を前に水中探査ロボットの最終点検の様子を公開しました。イルカのような形をした探査ロボットは、全長３メートル、重さは３５０キロあります。《はじめに》冒頭、安倍総理大臣は、ことしが明治元年から１５０年にあたることに触れ「明治という新しい時代が育てたあまたの人材が、技術優位の欧米諸国が迫る『国難』とも呼ぶべき危機の中で、わが国が急速に近代化を遂げる原動力となった。今また、日本は少子高齢化という『国難』とも呼ぶべき危機に直面している。もう１度、あらゆる日本人にチャンスを創ることで、少子高齢化も克服できる」と呼びかけました。《働き方改革》続いて安倍総理大臣は、具体的な政策課題の最初に「働き方改革」を取り上げ、「戦後の労働基準法制定以来、７０年ぶりの大改革だ。誰もが生きがいを感じて、その能力を思う存分発揮すれば少子高齢化も克服できる」と述べました。そして、同一労働同一賃金の実現や、時間外労働の上限規制の導入、それに労働時間でなく成果で評価するとして労働時間の規制から外す「高度プロフェッショナル制度」の創設などに取り組む考えを強調しました。".is_japanese())
     }
+    /// The assigned codepoints of the kana blocks as of Unicode 15, with whether they are kana
+    /// letters. The others, like iteration and voicing marks, are Japanese punctuation.
+    const ASSIGNED_KANA: [(u32, u32, bool); 16] = [
+        // Hiragana
+        (0x3041, 0x3096, true),
+        (0x3099, 0x309F, false),
+        // Katakana
+        (0x30A0, 0x30A0, false),
+        (0x30A1, 0x30FC, true),
+        (0x30FD, 0x30FF, false),
+        // Katakana Phonetic Extensions
+        (0x31F0, 0x31FF, true),
+        // Halfwidth katakana
+        (0xFF61, 0xFF65, false),
+        (0xFF66, 0xFF9F, true),
+        // Kana Extended-B
+        (0x1AFF0, 0x1AFF3, true),
+        (0x1AFF5, 0x1AFFB, true),
+        (0x1AFFD, 0x1AFFE, true),
+        // Kana Supplement and Kana Extended-A
+        (0x1B000, 0x1B122, true),
+        // Small Kana Extension
+        (0x1B132, 0x1B132, true),
+        (0x1B150, 0x1B152, true),
+        (0x1B155, 0x1B155, true),
+        (0x1B164, 0x1B167, true),
+    ];
+
+    #[test]
+    fn every_assigned_kana_codepoint_is_japanese() {
+        for (start, end, is_letter) in ASSIGNED_KANA {
+            for code in start..=end {
+                let text = char::from_u32(code).unwrap().to_string();
+                assert!(text.as_str().is_japanese(), "U+{code:04X}");
+                assert_eq!(text.as_str().is_kana(), is_letter, "U+{code:04X}");
+            }
+        }
+    }
+    #[test]
+    fn unassigned_kana_codepoints_are_not_japanese() {
+        for code in [
+            0x1AFF4, 0x1AFFC, 0x1AFFF, 0x1B123, 0x1B12F, 0x1B130, 0x1B133, 0x1B16F,
+        ] {
+            let text = char::from_u32(code).unwrap().to_string();
+            assert!(!text.as_str().is_japanese(), "U+{code:04X}");
+            assert!(!text.as_str().is_kana(), "U+{code:04X}");
+        }
+    }
 }
@@ -0,0 +1,46 @@
+use wana_kana::passport::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn family_name_is_upper_cased() {
+        assert_eq!(to_passport_name("すずき", "いちろう"), "SUZUKI Ichiro");
+    }
+
+    #[test]
+    fn accepts_katakana() {
+        assert_eq!(to_passport_name("サトウ", "ハナコ"), "SATO Hanako");
+    }
+
+    #[test]
+    fn assimilates_n() {
+        assert_eq!(to_passport_name("なんば", "じゅんぺい"), "NAMBA Jumpei");
+    }
+
+    #[test]
+    fn oh_spelling() {
+        assert_eq!(
+            to_passport_name_with_opt("おおた", "ゆうすけ", true),
+            "OHTA Yusuke"
+        );
+        assert_eq!(
+            to_passport_name_with_opt("おおた", "ゆうすけ", false),
+            "OTA Yusuke"
+        );
+    }
+
+    #[test]
+    fn empty_given_name() {
+        assert_eq!(to_passport_name("やまだ", ""), "YAMADA");
+    }
+
+    #[test]
+    fn empty_family_name() {
+        assert_eq!(to_passport_name("", "たろう"), "Taro");
+        assert_eq!(to_passport_name("  ", "たろう"), "Taro");
+        assert_eq!(to_passport_name("\u{3000}", "たろう"), "Taro");
+        assert_eq!(to_passport_name(" ", " "), "");
+    }
+}